@@ -0,0 +1,34 @@
+fn main() {
+    #[cfg(feature = "ffi")]
+    ffi::header();
+}
+
+#[cfg(feature = "ffi")]
+mod ffi {
+    use std::{ env, fs };
+    use std::path::PathBuf;
+
+    /// Writes `sarkara.h` to `OUT_DIR`.
+    ///
+    /// Set `SARKARA_GENERATE_HEADER` to also refresh the committed `include/sarkara.h`.
+    pub fn header() {
+        let dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+        let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("sarkara.h");
+
+        println!("cargo:rerun-if-changed=src/ffi/mod.rs");
+        println!("cargo:rerun-if-changed=cbindgen.toml");
+        println!("cargo:rerun-if-env-changed=SARKARA_GENERATE_HEADER");
+
+        cbindgen::Builder::new()
+            .with_crate(&dir)
+            .with_config(cbindgen::Config::from_file(dir.join("cbindgen.toml")).unwrap())
+            .generate()
+            .expect("unable to generate sarkara.h")
+            .write_to_file(&out);
+
+        if env::var_os("SARKARA_GENERATE_HEADER").is_some() {
+            fs::copy(&out, dir.join("include").join("sarkara.h"))
+                .expect("unable to write include/sarkara.h");
+        }
+    }
+}
@@ -0,0 +1,162 @@
+#ifndef SARKARA_H
+#define SARKARA_H
+
+/* Generated by cbindgen from src/ffi/mod.rs, do not edit. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+#define SARKARA_OK 0
+
+#define SARKARA_ERROR_LENGTH -1
+
+#define SARKARA_ERROR_DECODING -2
+
+#define SARKARA_ERROR_VERIFICATION_FAILED -3
+
+#define SARKARA_ERROR_INVALID_SIGNATURE -4
+
+#define SARKARA_ERROR_KEY_MISMATCH -5
+
+#define SARKARA_ERROR_WRONG_PASSPHRASE -6
+
+#define SARKARA_ERROR_NULL_POINTER -7
+
+#define SARKARA_ERROR_RNG -8
+
+#define SARKARA_ERROR_PANIC -9
+
+#define SARKARA_ERROR_INVALID_STATE -10
+
+size_t sarkara_kyber_private_key_bytes(void);
+
+size_t sarkara_kyber_public_key_bytes(void);
+
+size_t sarkara_kyber_message_bytes(void);
+
+size_t sarkara_kyber_shared_bytes(void);
+
+size_t sarkara_dilithium_private_key_bytes(void);
+
+size_t sarkara_dilithium_public_key_bytes(void);
+
+size_t sarkara_dilithium_signature_bytes(void);
+
+size_t sarkara_norx6441_key_bytes(void);
+
+size_t sarkara_norx6441_nonce_bytes(void);
+
+size_t sarkara_norx6441_tag_bytes(void);
+
+size_t sarkara_norxmrs_key_bytes(void);
+
+size_t sarkara_norxmrs_nonce_bytes(void);
+
+size_t sarkara_norxmrs_tag_bytes(void);
+
+int sarkara_kyber_keypair(uint8_t *sk, size_t sk_len, uint8_t *pk, size_t pk_len);
+
+int sarkara_kyber_encapsulate(const uint8_t *pk,
+                              size_t pk_len,
+                              uint8_t *shared,
+                              size_t shared_len,
+                              uint8_t *m,
+                              size_t m_len);
+
+int sarkara_kyber_decapsulate(const uint8_t *sk,
+                              size_t sk_len,
+                              const uint8_t *m,
+                              size_t m_len,
+                              uint8_t *shared,
+                              size_t shared_len);
+
+int sarkara_dilithium_keypair(uint8_t *sk, size_t sk_len, uint8_t *pk, size_t pk_len);
+
+int sarkara_dilithium_sign(const uint8_t *sk,
+                           size_t sk_len,
+                           const uint8_t *msg,
+                           size_t msg_len,
+                           uint8_t *sig,
+                           size_t sig_len);
+
+int sarkara_dilithium_verify(const uint8_t *pk,
+                             size_t pk_len,
+                             const uint8_t *msg,
+                             size_t msg_len,
+                             const uint8_t *sig,
+                             size_t sig_len);
+
+int sarkara_norx6441_seal(const uint8_t *key,
+                          size_t key_len,
+                          const uint8_t *nonce,
+                          size_t nonce_len,
+                          const uint8_t *aad,
+                          size_t aad_len,
+                          const uint8_t *input,
+                          size_t input_len,
+                          uint8_t *output,
+                          size_t output_len);
+
+int sarkara_norx6441_open(const uint8_t *key,
+                          size_t key_len,
+                          const uint8_t *nonce,
+                          size_t nonce_len,
+                          const uint8_t *aad,
+                          size_t aad_len,
+                          const uint8_t *input,
+                          size_t input_len,
+                          uint8_t *output,
+                          size_t output_len);
+
+int sarkara_norxmrs_seal(const uint8_t *key,
+                         size_t key_len,
+                         const uint8_t *nonce,
+                         size_t nonce_len,
+                         const uint8_t *aad,
+                         size_t aad_len,
+                         const uint8_t *input,
+                         size_t input_len,
+                         uint8_t *output,
+                         size_t output_len);
+
+int sarkara_norxmrs_open(const uint8_t *key,
+                         size_t key_len,
+                         const uint8_t *nonce,
+                         size_t nonce_len,
+                         const uint8_t *aad,
+                         size_t aad_len,
+                         const uint8_t *input,
+                         size_t input_len,
+                         uint8_t *output,
+                         size_t output_len);
+
+/**
+ * Kyber + Norx6441 sealed box, `m` receives the encapsulated key.
+ */
+int sarkara_sealedbox_send(const uint8_t *pk,
+                           size_t pk_len,
+                           uint8_t *m,
+                           size_t m_len,
+                           const uint8_t *nonce,
+                           size_t nonce_len,
+                           const uint8_t *aad,
+                           size_t aad_len,
+                           const uint8_t *input,
+                           size_t input_len,
+                           uint8_t *output,
+                           size_t output_len);
+
+int sarkara_sealedbox_recv(const uint8_t *sk,
+                           size_t sk_len,
+                           const uint8_t *m,
+                           size_t m_len,
+                           const uint8_t *nonce,
+                           size_t nonce_len,
+                           const uint8_t *aad,
+                           size_t aad_len,
+                           const uint8_t *input,
+                           size_t input_len,
+                           uint8_t *output,
+                           size_t output_len);
+
+#endif /* SARKARA_H */
@@ -1,4 +1,4 @@
-use crate::Error;
+use crate::{ Error, Length };
 
 pub mod norx6441;
 pub mod norx_mrs;
@@ -14,6 +14,15 @@ pub trait AeadCipher {
     /// TODO should be `Self::NONCE_LENGTH`
     fn seal(&self, nonce: &[u8], aad: &[u8], input: &[u8], output: &mut [u8]) -> Result<(), Error>;
     fn open(&self, nonce: &[u8], aad: &[u8], input: &[u8], output: &mut [u8]) -> Result<(), Error>;
+
+    /// Like `new`, but returns `Error::Length(Length::Key)` instead of panicking.
+    fn try_new(key: &[u8]) -> Result<Self, Error> where Self: Sized {
+        if key.len() != Self::KEY_LENGTH {
+            return Err(Error::Length(Length::Key));
+        }
+
+        Ok(Self::new(key))
+    }
 }
 
 
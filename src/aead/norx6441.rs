@@ -1,20 +1,21 @@
-use std::cmp;
+use core::cmp;
 use arrayref::{ array_ref, array_mut_ref };
 use norx::constant::{ KEY_LENGTH, NONCE_LENGTH, TAG_LENGTH, BLOCK_LENGTH };
 use norx::{ Norx as NorxCipher, Process, Encrypt, Decrypt };
-use crate::Error;
+use crate::{ Error, Length };
 use super::{ AeadCipher, Online, Encryption, Decryption };
 
 
 pub struct Norx6441([u8; KEY_LENGTH]);
 
+/// `process` is `None` if the nonce length is wrong, `finalize` reports it.
 pub struct EncryptProcess<'a> {
-    process: Process<Encrypt>,
+    process: Option<Process<Encrypt>>,
     key: &'a [u8; KEY_LENGTH]
 }
 
 pub struct DecryptProcess<'a> {
-    process: Process<Decrypt>,
+    process: Option<Process<Decrypt>>,
     key: &'a [u8; KEY_LENGTH]
 }
 
@@ -24,16 +25,26 @@ impl AeadCipher for Norx6441 {
     const TAG_LENGTH: usize = TAG_LENGTH;
 
     fn new(key: &[u8]) -> Self {
+        assert_eq!(key.len(), KEY_LENGTH, "norx6441: wrong key length");
+
         let mut k = [0; KEY_LENGTH];
         k.copy_from_slice(key);
         Norx6441(k)
     }
 
     fn seal(&self, nonce: &[u8], aad: &[u8], input: &[u8], output: &mut [u8]) -> Result<(), Error> {
+        if nonce.len() != NONCE_LENGTH {
+            return Err(Error::Length(Length::Nonce));
+        }
+
         self.encrypt(nonce, aad).finalize(input, output)
     }
 
     fn open(&self, nonce: &[u8], aad: &[u8], input: &[u8], output: &mut [u8]) -> Result<(), Error> {
+        if nonce.len() != NONCE_LENGTH {
+            return Err(Error::Length(Length::Nonce));
+        }
+
         self.decrypt(nonce, aad).finalize(input, output)
     }
 }
@@ -43,17 +54,29 @@ impl<'a> Online<'a> for Norx6441 {
     type Decryption = DecryptProcess<'a>;
 
     fn encrypt(&'a self, nonce: &[u8], aad: &[u8]) -> Self::Encryption {
-        let nonce = array_ref!(nonce, 0, NONCE_LENGTH);
+        let process = if nonce.len() == NONCE_LENGTH {
+            let nonce = array_ref!(nonce, 0, NONCE_LENGTH);
+            Some(NorxCipher::new(&self.0, nonce).encrypt(aad))
+        } else {
+            None
+        };
+
         EncryptProcess {
-            process: NorxCipher::new(&self.0, nonce).encrypt(aad),
+            process,
             key: &self.0
         }
     }
 
     fn decrypt(&'a self, nonce: &[u8], aad: &[u8]) -> Self::Decryption {
-        let nonce = array_ref!(nonce, 0, NONCE_LENGTH);
+        let process = if nonce.len() == NONCE_LENGTH {
+            let nonce = array_ref!(nonce, 0, NONCE_LENGTH);
+            Some(NorxCipher::new(&self.0, nonce).decrypt(aad))
+        } else {
+            None
+        };
+
         DecryptProcess {
-            process: NorxCipher::new(&self.0, nonce).decrypt(aad),
+            process,
             key: &self.0
         }
     }
@@ -61,12 +84,17 @@ impl<'a> Online<'a> for Norx6441 {
 
 impl<'a> Encryption<'a> for EncryptProcess<'a> {
     fn process<'b>(&mut self, input: &[u8], output: &'b mut [u8]) -> &'b [u8] {
+        let process = match self.process.as_mut() {
+            Some(process) => process,
+            None => return &output[..0]
+        };
+
         let len = cmp::min(input.len(), output.len());
 
         let (input, _) = input.split_at(len - len % BLOCK_LENGTH);
         let (output, _) = output.split_at_mut(input.len());
 
-        self.process.process(
+        process.process(
             input.chunks(BLOCK_LENGTH)
                 .zip(output.chunks_mut(BLOCK_LENGTH))
                 .map(|(input, output)| (
@@ -80,13 +108,16 @@ impl<'a> Encryption<'a> for EncryptProcess<'a> {
 
     fn finalize(mut self, input: &[u8], output: &mut [u8]) -> Result<(), Error> {
         if input.len() + TAG_LENGTH != output.len() {
-            return Err(Error::Length);
+            return Err(Error::Length(Length::Output));
+        }
+        if self.process.is_none() {
+            return Err(Error::Length(Length::Nonce));
         }
 
         let take = self.process(input, output).len();
         let (_, input) = input.split_at(take);
         let (_, output) = output.split_at_mut(take);
-        self.process.finalize(self.key, &[], input, output);
+        self.process.expect("checked").finalize(self.key, &[], input, output);
 
         Ok(())
     }
@@ -94,12 +125,17 @@ impl<'a> Encryption<'a> for EncryptProcess<'a> {
 
 impl<'a> Decryption<'a> for DecryptProcess<'a> {
     fn process<'b>(&mut self, input: &[u8], output: &'b mut [u8]) -> &'b [u8] {
+        let process = match self.process.as_mut() {
+            Some(process) => process,
+            None => return &output[..0]
+        };
+
         let len = cmp::min(input.len(), output.len());
 
         let (input, _) = input.split_at(len - len % BLOCK_LENGTH);
         let (output, _) = output.split_at_mut(input.len());
 
-        self.process.process(
+        process.process(
             input.chunks(BLOCK_LENGTH)
                 .zip(output.chunks_mut(BLOCK_LENGTH))
                 .map(|(input, output)| (
@@ -113,14 +149,17 @@ impl<'a> Decryption<'a> for DecryptProcess<'a> {
 
     fn finalize(mut self, input: &[u8], output: &mut [u8]) -> Result<(), Error> {
         if input.len() != output.len() + TAG_LENGTH {
-            return Err(Error::Length);
+            return Err(Error::Length(Length::Output));
+        }
+        if self.process.is_none() {
+            return Err(Error::Length(Length::Nonce));
         }
 
         let take = self.process(input, output).len();
         let (_, input) = input.split_at(take);
         let (_, output) = output.split_at_mut(take);
 
-        if self.process.finalize(self.key, &[], input, output) {
+        if self.process.expect("checked").finalize(self.key, &[], input, output) {
             Ok(())
         } else {
             Err(Error::VerificationFailed)
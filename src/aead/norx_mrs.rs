@@ -4,7 +4,7 @@ use mem_aead_mrs::{
     KEY_LENGTH, NONCE_LENGTH, TAG_LENGTH,
     Mrs, Permutation
 };
-use crate::Error;
+use crate::{ Error, Length };
 use super::AeadCipher;
 
 
@@ -22,10 +22,12 @@ impl AeadCipher for NorxMRS {
     }
 
     fn seal(&self, nonce: &[u8], aad: &[u8], input: &[u8], output: &mut [u8]) -> Result<(), Error> {
-        if nonce.len() != Self::NONCE_LENGTH ||
-            input.len() + Self::TAG_LENGTH != output.len()
-        {
-            return Err(Error::Length);
+        if nonce.len() != Self::NONCE_LENGTH {
+            return Err(Error::Length(Length::Nonce));
+        }
+
+        if input.len() + Self::TAG_LENGTH != output.len() {
+            return Err(Error::Length(Length::Output));
         }
 
         let (output, tag) = output.split_at_mut(input.len());
@@ -41,10 +43,12 @@ impl AeadCipher for NorxMRS {
     }
 
     fn open(&self, nonce: &[u8], aad: &[u8], input: &[u8], output: &mut [u8]) -> Result<(), Error> {
-        if nonce.len() != Self::NONCE_LENGTH ||
-            input.len() != output.len() + Self::TAG_LENGTH
-        {
-            return Err(Error::Length);
+        if nonce.len() != Self::NONCE_LENGTH {
+            return Err(Error::Length(Length::Nonce));
+        }
+
+        if input.len() != output.len() + Self::TAG_LENGTH {
+            return Err(Error::Length(Length::Output));
         }
 
         let (input, tag) = input.split_at(output.len());
@@ -0,0 +1,103 @@
+use core::cmp;
+use alloc::format;
+use alloc::string::String;
+use crate::hash::{ Xof, XofReader };
+use crate::hash::norx::NorxHash;
+use crate::common::eq_ct;
+use crate::{ Packing, Error, Length, Decoding };
+
+mod base64;
+
+
+const LINE_LENGTH: usize = 64;
+const CHECKSUM_LENGTH: usize = 4;
+const ARMOR: u8 = 0x07;
+
+pub trait Armored: Packing {
+    const LABEL: &'static str;
+}
+
+pub fn encode<T: Armored>(t: &T) -> String {
+    let (body, checksum) = t.read_bytes(|bytes| (
+        base64::encode(bytes),
+        base64::encode(&checksum(T::LABEL, bytes))
+    ));
+
+    let mut output = boundary("BEGIN", T::LABEL);
+    output.push('\n');
+    let mut body = body.as_str();
+    while !body.is_empty() {
+        let (line, rest) = body.split_at(cmp::min(LINE_LENGTH, body.len()));
+        output.push_str(line);
+        output.push('\n');
+        body = rest;
+    }
+    output.push('=');
+    output.push_str(&checksum);
+    output.push('\n');
+    output.push_str(&boundary("END", T::LABEL));
+    output.push('\n');
+    output
+}
+
+pub fn decode<T: Armored>(input: &str) -> Result<T, Error> {
+    let mut lines = input.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty());
+
+    match lines.next() {
+        Some(line) if line == boundary("BEGIN", T::LABEL) => (),
+        Some(line) if line.starts_with("-----BEGIN ") => return Err(Error::Decoding(Decoding::Label)),
+        _ => return Err(Error::Decoding(Decoding::Malformed))
+    }
+
+    let mut body = String::new();
+    let mut checksum = None;
+    let mut end = false;
+    for line in lines.by_ref() {
+        if line.starts_with("-----") {
+            end = line == boundary("END", T::LABEL);
+            break
+        } else if let Some(sum) = line.strip_prefix('=') {
+            checksum = Some(sum);
+        } else if checksum.is_none() {
+            body.push_str(line);
+        } else {
+            return Err(Error::Decoding(Decoding::Malformed));
+        }
+    }
+
+    if !end || lines.next().is_some() {
+        return Err(Error::Decoding(Decoding::Malformed));
+    }
+
+    let bytes = base64::decode(&body)
+        .ok_or(Error::Decoding(Decoding::Malformed))?;
+    let checksum = checksum
+        .and_then(base64::decode)
+        .ok_or(Error::Decoding(Decoding::Malformed))?;
+
+    if bytes.len() != T::BYTES_LENGTH {
+        return Err(Error::Length(Length::Encoding));
+    }
+
+    if !eq_ct(&checksum, &self::checksum(T::LABEL, &bytes)) {
+        return Err(Error::Decoding(Decoding::Checksum));
+    }
+
+    Ok(T::from_bytes(&bytes))
+}
+
+fn boundary(kind: &str, label: &str) -> String {
+    format!("-----{} {}-----", kind, label)
+}
+
+fn checksum(label: &str, bytes: &[u8]) -> [u8; CHECKSUM_LENGTH] {
+    let mut hasher = NorxHash::with_domain(ARMOR);
+    hasher.update_framed(label.as_bytes());
+    hasher.update_framed(bytes);
+
+    let mut output = [0; CHECKSUM_LENGTH];
+    hasher.xof().read(&mut output);
+    output
+}
@@ -0,0 +1,339 @@
+//! Command-line front end for keygen, sign, verify, seal and open.
+
+use std::{ env, fmt, fs, process };
+use std::io::{ self, Read, Write };
+use std::path::Path;
+use sarkara::{ Packing, Error as CryptoError, Length };
+use sarkara::armor::{ self, Armored };
+use sarkara::aead::{ AeadCipher, Encryption, Decryption, norx6441::Norx6441 };
+use sarkara::kex::{ KeyExchange, kyber::Kyber };
+use sarkara::sign::{ Signature, dilithium::Dilithium };
+use sarkara::sealedbox::SealedBox;
+use sarkara::rand::norx::NorxRng;
+
+
+const USAGE: &str = "\
+usage: sarkara [--armor] <command> <args>
+
+commands:
+    keygen kyber <secret key> <public key>
+    keygen dilithium <secret key> <public key>
+    sign <secret key> <file> <signature>
+    verify <public key> <file> <signature>
+    seal <public key> <input> <output>
+    open <secret key> <input> <output>
+
+`--armor` writes keys and signatures armored, raw bytes otherwise.
+Both encodings are accepted on input. `-` is stdin or stdout,
+except for the output of `open`, which is only written once the ciphertext is verified.
+
+exit status:
+    0  success
+    1  signature or ciphertext failed to verify
+    2  usage error
+    3  i/o error
+    4  malformed key, signature or ciphertext";
+
+const CHUNK_LENGTH: usize = 1 << 16;
+
+type Sealed = SealedBox<Kyber, Norx6441>;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Encoding {
+    Raw,
+    Armor
+}
+
+enum Error {
+    Usage(&'static str),
+    Io(String, io::Error),
+    Crypto(CryptoError)
+}
+
+impl Error {
+    fn code(&self) -> i32 {
+        match self {
+            Error::Crypto(CryptoError::InvalidSignature)
+                | Error::Crypto(CryptoError::VerificationFailed) => 1,
+            Error::Usage(_) => 2,
+            Error::Io(..) => 3,
+            Error::Crypto(_) => 4
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Usage(err) => write!(f, "{}\n\n{}", err, USAGE),
+            Error::Io(path, err) => write!(f, "{}: {}", path, err),
+            Error::Crypto(err) => err.fmt(f)
+        }
+    }
+}
+
+impl From<CryptoError> for Error {
+    fn from(err: CryptoError) -> Error {
+        Error::Crypto(err)
+    }
+}
+
+
+fn main() {
+    let mut encoding = Encoding::Raw;
+    let args = env::args()
+        .skip(1)
+        .filter(|arg| if arg == "--armor" {
+            encoding = Encoding::Armor;
+            false
+        } else {
+            true
+        })
+        .collect::<Vec<_>>();
+    let args = args.iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+
+    let result = match args.as_slice() {
+        ["keygen", "kyber", sk, pk] => keygen::<Kyber>(encoding, sk, pk),
+        ["keygen", "dilithium", sk, pk] => keygen::<Dilithium>(encoding, sk, pk),
+        ["sign", sk, input, sig] => sign(encoding, sk, input, sig),
+        ["verify", pk, input, sig] => verify(pk, input, sig),
+        ["seal", pk, input, output] => seal(pk, input, output),
+        ["open", _, _, "-"] => Err(Error::Usage("open can not write to stdout")),
+        ["open", sk, input, output] => open(sk, input, output),
+        ["help"] | ["--help"] | ["-h"] => {
+            println!("{}", USAGE);
+            Ok(())
+        },
+        _ => Err(Error::Usage("invalid arguments"))
+    };
+
+    if let Err(err) = result {
+        eprintln!("sarkara: {}", err);
+        process::exit(err.code());
+    }
+}
+
+
+trait Keypair {
+    type PrivateKey: Armored;
+    type PublicKey: Armored;
+
+    fn keypair(rng: &mut NorxRng) -> (Self::PrivateKey, Self::PublicKey);
+}
+
+impl Keypair for Kyber {
+    type PrivateKey = <Kyber as KeyExchange>::PrivateKey;
+    type PublicKey = <Kyber as KeyExchange>::PublicKey;
+
+    fn keypair(rng: &mut NorxRng) -> (Self::PrivateKey, Self::PublicKey) {
+        <Kyber as KeyExchange>::keypair(rng)
+    }
+}
+
+impl Keypair for Dilithium {
+    type PrivateKey = <Dilithium as Signature>::PrivateKey;
+    type PublicKey = <Dilithium as Signature>::PublicKey;
+
+    fn keypair(rng: &mut NorxRng) -> (Self::PrivateKey, Self::PublicKey) {
+        <Dilithium as Signature>::keypair(rng)
+    }
+}
+
+fn keygen<K: Keypair>(encoding: Encoding, sk_path: &str, pk_path: &str) -> Result<(), Error> {
+    let (sk, pk) = K::keypair(&mut rng()?);
+    write_secret(sk_path, &encode(encoding, &sk))?;
+    write_file(pk_path, &encode(encoding, &pk))
+}
+
+fn sign(encoding: Encoding, sk_path: &str, input: &str, sig_path: &str) -> Result<(), Error> {
+    let sk = read_key::<<Dilithium as Signature>::PrivateKey>(sk_path)?;
+    let data = read_file(input)?;
+    let sig = Dilithium::signature(rng()?, &sk, &data);
+    write_file(sig_path, &encode(encoding, &sig))
+}
+
+fn verify(pk_path: &str, input: &str, sig_path: &str) -> Result<(), Error> {
+    let pk = read_key::<<Dilithium as Signature>::PublicKey>(pk_path)?;
+    let sig = read_key::<<Dilithium as Signature>::Signature>(sig_path)?;
+    let data = read_file(input)?;
+    Dilithium::verify(&pk, &sig, &data)?;
+    Ok(())
+}
+
+/// Output is `message || ciphertext || tag`, the key is single use so the nonce is zero.
+fn seal(pk_path: &str, input: &str, output: &str) -> Result<(), Error> {
+    let pk = read_key::<<Kyber as KeyExchange>::PublicKey>(pk_path)?;
+    let (m, sealing) = Sealed::send(rng()?, &pk);
+    let nonce = [0; Norx6441::NONCE_LENGTH];
+
+    let mut reader = open_reader(input)?;
+    let mut writer = open_writer(output)?;
+    let mut buf = vec![0; CHUNK_LENGTH];
+    let mut pending = Vec::with_capacity(2 * CHUNK_LENGTH);
+    let mut out = vec![0; 2 * CHUNK_LENGTH + Norx6441::TAG_LENGTH];
+
+    m.read_bytes(|m| writer.write_all(m))
+        .map_err(|err| Error::Io(output.into(), err))?;
+
+    let mut process = sealing.encrypt(&nonce, &[]);
+    loop {
+        let n = reader.read(&mut buf)
+            .map_err(|err| Error::Io(input.into(), err))?;
+        if n == 0 {
+            break
+        }
+        pending.extend_from_slice(&buf[..n]);
+
+        let take = process.process(&pending, &mut out).len();
+        writer.write_all(&out[..take])
+            .map_err(|err| Error::Io(output.into(), err))?;
+        pending.drain(..take);
+    }
+
+    let out = &mut out[..pending.len() + Norx6441::TAG_LENGTH];
+    process.finalize(&pending, out)?;
+    writer.write_all(out)
+        .and_then(|_| writer.flush())
+        .map_err(|err| Error::Io(output.into(), err))
+}
+
+/// Plaintext is decrypted to `<output>.tmp` and renamed once the tag is checked,
+/// so `output` can not be stdout.
+fn open(sk_path: &str, input: &str, output: &str) -> Result<(), Error> {
+    type Message = <Kyber as KeyExchange>::Message;
+
+    let sk = read_key::<<Kyber as KeyExchange>::PrivateKey>(sk_path)?;
+    let nonce = [0; Norx6441::NONCE_LENGTH];
+
+    let mut reader = open_reader(input)?;
+    let mut m = vec![0; Message::BYTES_LENGTH];
+    reader.read_exact(&mut m)
+        .map_err(|err| match err.kind() {
+            io::ErrorKind::UnexpectedEof => Error::Crypto(CryptoError::Length(Length::Encoding)),
+            _ => Error::Io(input.into(), err)
+        })?;
+    let opening = Sealed::checked_recv(&sk, &Message::from_bytes(&m))?;
+
+    // plaintext is written to a temporary file until the tag is verified
+    let tmp = format!("{}.tmp", output);
+    let mut writer = open_writer(&tmp)?;
+    let mut process = opening.decrypt(&nonce, &[]);
+    let mut buf = vec![0; CHUNK_LENGTH];
+    let mut pending = Vec::with_capacity(2 * CHUNK_LENGTH);
+    let mut out = vec![0; 2 * CHUNK_LENGTH];
+
+    let mut result = (|| loop {
+        let n = reader.read(&mut buf)
+            .map_err(|err| Error::Io(input.into(), err))?;
+        if n == 0 {
+            break Ok(())
+        }
+        pending.extend_from_slice(&buf[..n]);
+
+        // hold back what may be the tag
+        let end = pending.len().saturating_sub(Norx6441::TAG_LENGTH);
+        let take = process.process(&pending[..end], &mut out).len();
+        writer.write_all(&out[..take])
+            .map_err(|err| Error::Io(output.into(), err))?;
+        pending.drain(..take);
+    })();
+
+    if result.is_ok() {
+        result = pending.len().checked_sub(Norx6441::TAG_LENGTH)
+            .ok_or(CryptoError::Length(Length::Encoding))
+            .and_then(|len| process.finalize(&pending, &mut out[..len]).map(|_| len))
+            .map_err(Error::from)
+            .and_then(|len| writer.write_all(&out[..len])
+                .and_then(|_| writer.flush())
+                .map_err(|err| Error::Io(output.into(), err))
+            );
+    }
+
+    drop(writer);
+    if result.is_ok() {
+        result = fs::rename(&tmp, output)
+            .map_err(|err| Error::Io(output.into(), err));
+    } else {
+        let _ = fs::remove_file(&tmp);
+    }
+
+    result
+}
+
+
+fn rng() -> Result<NorxRng, Error> {
+    NorxRng::new()
+        .map_err(|err| Error::Io("entropy".into(), io::Error::new(io::ErrorKind::Other, err)))
+}
+
+fn encode<T: Armored>(encoding: Encoding, t: &T) -> Vec<u8> {
+    match encoding {
+        Encoding::Raw => t.read_bytes(|bytes| bytes.to_vec()),
+        Encoding::Armor => armor::encode(t).into_bytes()
+    }
+}
+
+fn read_key<T: Armored>(path: &str) -> Result<T, Error> {
+    let bytes = read_file(path)?;
+
+    if bytes.starts_with(b"-----BEGIN ") {
+        let text = String::from_utf8(bytes)
+            .map_err(|_| CryptoError::Decoding(sarkara::Decoding::Malformed))?;
+        Ok(armor::decode(&text)?)
+    } else if bytes.len() == T::BYTES_LENGTH {
+        Ok(T::from_bytes(&bytes))
+    } else {
+        Err(CryptoError::Length(Length::Encoding).into())
+    }
+}
+
+fn read_file(path: &str) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::new();
+    open_reader(path)?
+        .read_to_end(&mut bytes)
+        .map_err(|err| Error::Io(path.into(), err))?;
+    Ok(bytes)
+}
+
+fn write_file(path: &str, bytes: &[u8]) -> Result<(), Error> {
+    let mut writer = open_writer(path)?;
+    writer.write_all(bytes)
+        .and_then(|_| writer.flush())
+        .map_err(|err| Error::Io(path.into(), err))
+}
+
+fn write_secret(path: &str, bytes: &[u8]) -> Result<(), Error> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+
+    #[cfg(unix)] {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    options.open(Path::new(path))
+        .and_then(|mut fd| fd.write_all(bytes))
+        .map_err(|err| Error::Io(path.into(), err))
+}
+
+fn open_reader(path: &str) -> Result<Box<dyn Read>, Error> {
+    if path == "-" {
+        Ok(Box::new(io::stdin()))
+    } else {
+        fs::File::open(path)
+            .map(|fd| Box::new(io::BufReader::new(fd)) as Box<dyn Read>)
+            .map_err(|err| Error::Io(path.into(), err))
+    }
+}
+
+fn open_writer(path: &str) -> Result<Box<dyn Write>, Error> {
+    if path == "-" {
+        Ok(Box::new(io::stdout()))
+    } else {
+        fs::File::create(path)
+            .map(|fd| Box::new(io::BufWriter::new(fd)) as Box<dyn Write>)
+            .map_err(|err| Error::Io(path.into(), err))
+    }
+}
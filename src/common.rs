@@ -12,6 +12,9 @@ macro_rules! eq {
 
 macro_rules! packing {
     ( $t:ident ; $len:expr ) => {
+        packing!($t; $len; );
+    };
+    ( $t:ident ; $len:expr ; $( $field:expr ),* ) => {
         impl Packing for $t {
             const BYTES_LENGTH: usize = $len;
 
@@ -26,12 +29,21 @@ macro_rules! packing {
                 let buf = arrayref::array_ref!(buf, 0, $len);
                 let mut pk = [0; $len];
                 pk.clone_from(buf);
-                $t(pk)
+                $t(pk $( , $field )*)
             }
         }
     };
 }
 
+macro_rules! armored {
+    ( $t:ident ; $label:expr ) => {
+        #[cfg(feature = "alloc")]
+        impl crate::armor::Armored for $t {
+            const LABEL: &'static str = $label;
+        }
+    };
+}
+
 #[cfg(feature = "serde")]
 macro_rules! serde {
     ( $t:ident ) => {
@@ -72,3 +84,43 @@ macro_rules! serde {
         }
     }
 }
+
+pub fn eq_ct(x: &[u8], y: &[u8]) -> bool {
+    x.len() == y.len() &&
+        x.iter().zip(y).fold(0, |sum, (a, b)| sum | (a ^ b)) == 0
+}
+
+/// Volatile writes, so the compiler can not drop them as dead stores.
+pub fn zero<T: Copy + Default>(buf: &mut [T]) {
+    use core::ptr;
+    use core::sync::atomic::{ self, Ordering };
+
+    for b in buf.iter_mut() {
+        unsafe { ptr::write_volatile(b, T::default()) };
+    }
+    atomic::compiler_fence(Ordering::SeqCst);
+}
+
+/// Map `f` over `items` on all available threads, keeping the order.
+#[cfg(feature = "parallel")]
+pub fn par_map<T, R, F>(items: &[T], f: F) -> Vec<R>
+    where
+        T: Sync,
+        R: Send,
+        F: Fn(&T) -> R + Sync
+{
+    use std::thread;
+
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk = ((items.len() + threads - 1) / threads).max(1);
+    let f = &f;
+
+    thread::scope(|s| {
+        items.chunks(chunk)
+            .map(|chunk| s.spawn(move || chunk.iter().map(f).collect::<Vec<_>>()))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    })
+}
@@ -0,0 +1,348 @@
+//! C interface.
+//!
+//! Every buffer is passed as a pointer and an explicit length, a length that
+//! does not match the algorithm is rejected with `SARKARA_ERROR_LENGTH`.
+//! Functions return `SARKARA_OK` or a negative error code.
+//!
+//! All functions are `unsafe`: each pointer must be valid for its length,
+//! a null pointer is only accepted together with a zero length.
+
+#![allow(clippy::missing_safety_doc)]
+
+use std::{ slice, panic };
+use std::os::raw::c_int;
+use crate::aead::{ AeadCipher, norx6441::Norx6441, norx_mrs::NorxMRS };
+use crate::kex::{ KeyExchange, CheckedExchange, kyber::Kyber };
+use crate::sign::{ Signature, DeterministicSignature, dilithium::Dilithium };
+use crate::sealedbox::SealedBox;
+use crate::rand::norx::NorxRng;
+use crate::{ Packing, Error };
+
+
+pub const SARKARA_OK: c_int = 0;
+pub const SARKARA_ERROR_LENGTH: c_int = -1;
+pub const SARKARA_ERROR_DECODING: c_int = -2;
+pub const SARKARA_ERROR_VERIFICATION_FAILED: c_int = -3;
+pub const SARKARA_ERROR_INVALID_SIGNATURE: c_int = -4;
+pub const SARKARA_ERROR_KEY_MISMATCH: c_int = -5;
+pub const SARKARA_ERROR_WRONG_PASSPHRASE: c_int = -6;
+pub const SARKARA_ERROR_NULL_POINTER: c_int = -7;
+pub const SARKARA_ERROR_RNG: c_int = -8;
+pub const SARKARA_ERROR_PANIC: c_int = -9;
+pub const SARKARA_ERROR_INVALID_STATE: c_int = -10;
+
+type Kem = SealedBox<Kyber, Norx6441>;
+
+
+#[no_mangle] pub extern "C" fn sarkara_kyber_private_key_bytes() -> usize { <Kyber as KeyExchange>::PrivateKey::BYTES_LENGTH }
+#[no_mangle] pub extern "C" fn sarkara_kyber_public_key_bytes() -> usize { <Kyber as KeyExchange>::PublicKey::BYTES_LENGTH }
+#[no_mangle] pub extern "C" fn sarkara_kyber_message_bytes() -> usize { <Kyber as KeyExchange>::Message::BYTES_LENGTH }
+#[no_mangle] pub extern "C" fn sarkara_kyber_shared_bytes() -> usize { Kyber::SHARED_LENGTH }
+
+#[no_mangle] pub extern "C" fn sarkara_dilithium_private_key_bytes() -> usize { <Dilithium as Signature>::PrivateKey::BYTES_LENGTH }
+#[no_mangle] pub extern "C" fn sarkara_dilithium_public_key_bytes() -> usize { <Dilithium as Signature>::PublicKey::BYTES_LENGTH }
+#[no_mangle] pub extern "C" fn sarkara_dilithium_signature_bytes() -> usize { <Dilithium as Signature>::Signature::BYTES_LENGTH }
+
+#[no_mangle] pub extern "C" fn sarkara_norx6441_key_bytes() -> usize { Norx6441::KEY_LENGTH }
+#[no_mangle] pub extern "C" fn sarkara_norx6441_nonce_bytes() -> usize { Norx6441::NONCE_LENGTH }
+#[no_mangle] pub extern "C" fn sarkara_norx6441_tag_bytes() -> usize { Norx6441::TAG_LENGTH }
+
+#[no_mangle] pub extern "C" fn sarkara_norxmrs_key_bytes() -> usize { NorxMRS::KEY_LENGTH }
+#[no_mangle] pub extern "C" fn sarkara_norxmrs_nonce_bytes() -> usize { NorxMRS::NONCE_LENGTH }
+#[no_mangle] pub extern "C" fn sarkara_norxmrs_tag_bytes() -> usize { NorxMRS::TAG_LENGTH }
+
+
+#[no_mangle]
+pub unsafe extern "C" fn sarkara_kyber_keypair(
+    sk: *mut u8, sk_len: usize,
+    pk: *mut u8, pk_len: usize
+) -> c_int {
+    status(|| {
+        let sk_buf = output(sk, sk_len)?;
+        let pk_buf = output(pk, pk_len)?;
+        check::<<Kyber as KeyExchange>::PrivateKey>(sk_buf)?;
+        check::<<Kyber as KeyExchange>::PublicKey>(pk_buf)?;
+
+        let (sk, pk) = Kyber::keypair(rng()?);
+        write(&sk, sk_buf)?;
+        write(&pk, pk_buf)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn sarkara_kyber_encapsulate(
+    pk: *const u8, pk_len: usize,
+    shared: *mut u8, shared_len: usize,
+    m: *mut u8, m_len: usize
+) -> c_int {
+    status(|| {
+        let pk = read::<<Kyber as KeyExchange>::PublicKey>(input(pk, pk_len)?)?;
+        let shared = output(shared, shared_len)?;
+        let m_buf = output(m, m_len)?;
+        check::<<Kyber as KeyExchange>::Message>(m_buf)?;
+        if shared.len() != Kyber::SHARED_LENGTH {
+            return Err(SARKARA_ERROR_LENGTH);
+        }
+
+        let m = Kyber::exchange_to(rng()?, shared, &pk);
+        write(&m, m_buf)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn sarkara_kyber_decapsulate(
+    sk: *const u8, sk_len: usize,
+    m: *const u8, m_len: usize,
+    shared: *mut u8, shared_len: usize
+) -> c_int {
+    status(|| {
+        let sk = read::<<Kyber as KeyExchange>::PrivateKey>(input(sk, sk_len)?)?;
+        let m = read::<<Kyber as KeyExchange>::Message>(input(m, m_len)?)?;
+        let shared = output(shared, shared_len)?;
+        if shared.len() != Kyber::SHARED_LENGTH {
+            return Err(SARKARA_ERROR_LENGTH);
+        }
+
+        <Kyber as CheckedExchange>::exchange_from(shared, &sk, &m)
+            .map_err(code)
+    })
+}
+
+
+#[no_mangle]
+pub unsafe extern "C" fn sarkara_dilithium_keypair(
+    sk: *mut u8, sk_len: usize,
+    pk: *mut u8, pk_len: usize
+) -> c_int {
+    status(|| {
+        let sk_buf = output(sk, sk_len)?;
+        let pk_buf = output(pk, pk_len)?;
+        check::<<Dilithium as Signature>::PrivateKey>(sk_buf)?;
+        check::<<Dilithium as Signature>::PublicKey>(pk_buf)?;
+
+        let (sk, pk) = Dilithium::keypair(rng()?);
+        write(&sk, sk_buf)?;
+        write(&pk, pk_buf)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn sarkara_dilithium_sign(
+    sk: *const u8, sk_len: usize,
+    msg: *const u8, msg_len: usize,
+    sig: *mut u8, sig_len: usize
+) -> c_int {
+    status(|| {
+        let sk = read::<<Dilithium as Signature>::PrivateKey>(input(sk, sk_len)?)?;
+        let msg = input(msg, msg_len)?;
+        let sig_buf = output(sig, sig_len)?;
+        check::<<Dilithium as Signature>::Signature>(sig_buf)?;
+
+        let sig = <Dilithium as DeterministicSignature>::signature(&sk, msg);
+        write(&sig, sig_buf)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn sarkara_dilithium_verify(
+    pk: *const u8, pk_len: usize,
+    msg: *const u8, msg_len: usize,
+    sig: *const u8, sig_len: usize
+) -> c_int {
+    status(|| {
+        let pk = read::<<Dilithium as Signature>::PublicKey>(input(pk, pk_len)?)?;
+        let msg = input(msg, msg_len)?;
+        let sig = read::<<Dilithium as Signature>::Signature>(input(sig, sig_len)?)?;
+
+        Dilithium::verify(&pk, &sig, msg)
+            .map_err(code)
+    })
+}
+
+
+#[no_mangle]
+pub unsafe extern "C" fn sarkara_norx6441_seal(
+    key: *const u8, key_len: usize,
+    nonce: *const u8, nonce_len: usize,
+    aad: *const u8, aad_len: usize,
+    input: *const u8, input_len: usize,
+    output: *mut u8, output_len: usize
+) -> c_int {
+    aead::<Norx6441>(true, key, key_len, nonce, nonce_len, aad, aad_len, input, input_len, output, output_len)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn sarkara_norx6441_open(
+    key: *const u8, key_len: usize,
+    nonce: *const u8, nonce_len: usize,
+    aad: *const u8, aad_len: usize,
+    input: *const u8, input_len: usize,
+    output: *mut u8, output_len: usize
+) -> c_int {
+    aead::<Norx6441>(false, key, key_len, nonce, nonce_len, aad, aad_len, input, input_len, output, output_len)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn sarkara_norxmrs_seal(
+    key: *const u8, key_len: usize,
+    nonce: *const u8, nonce_len: usize,
+    aad: *const u8, aad_len: usize,
+    input: *const u8, input_len: usize,
+    output: *mut u8, output_len: usize
+) -> c_int {
+    aead::<NorxMRS>(true, key, key_len, nonce, nonce_len, aad, aad_len, input, input_len, output, output_len)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn sarkara_norxmrs_open(
+    key: *const u8, key_len: usize,
+    nonce: *const u8, nonce_len: usize,
+    aad: *const u8, aad_len: usize,
+    input: *const u8, input_len: usize,
+    output: *mut u8, output_len: usize
+) -> c_int {
+    aead::<NorxMRS>(false, key, key_len, nonce, nonce_len, aad, aad_len, input, input_len, output, output_len)
+}
+
+
+/// Kyber + Norx6441 sealed box, `m` receives the encapsulated key.
+#[no_mangle]
+pub unsafe extern "C" fn sarkara_sealedbox_send(
+    pk: *const u8, pk_len: usize,
+    m: *mut u8, m_len: usize,
+    nonce: *const u8, nonce_len: usize,
+    aad: *const u8, aad_len: usize,
+    input: *const u8, input_len: usize,
+    output: *mut u8, output_len: usize
+) -> c_int {
+    status(|| {
+        let pk = read::<<Kyber as KeyExchange>::PublicKey>(self::input(pk, pk_len)?)?;
+        let m_buf = self::output(m, m_len)?;
+        check::<<Kyber as KeyExchange>::Message>(m_buf)?;
+        let nonce = self::input(nonce, nonce_len)?;
+        let aad = self::input(aad, aad_len)?;
+        let input = self::input(input, input_len)?;
+        let output = self::output(output, output_len)?;
+        if nonce.len() != Norx6441::NONCE_LENGTH {
+            return Err(SARKARA_ERROR_LENGTH);
+        }
+
+        let (m, sealing) = Kem::send(rng()?, &pk);
+        sealing.seal(nonce, aad, input, output).map_err(code)?;
+        write(&m, m_buf)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn sarkara_sealedbox_recv(
+    sk: *const u8, sk_len: usize,
+    m: *const u8, m_len: usize,
+    nonce: *const u8, nonce_len: usize,
+    aad: *const u8, aad_len: usize,
+    input: *const u8, input_len: usize,
+    output: *mut u8, output_len: usize
+) -> c_int {
+    status(|| {
+        let sk = read::<<Kyber as KeyExchange>::PrivateKey>(self::input(sk, sk_len)?)?;
+        let m = read::<<Kyber as KeyExchange>::Message>(self::input(m, m_len)?)?;
+        let nonce = self::input(nonce, nonce_len)?;
+        let aad = self::input(aad, aad_len)?;
+        let input = self::input(input, input_len)?;
+        let output = self::output(output, output_len)?;
+        if nonce.len() != Norx6441::NONCE_LENGTH {
+            return Err(SARKARA_ERROR_LENGTH);
+        }
+
+        let opening = Kem::checked_recv(&sk, &m).map_err(code)?;
+        opening.open(nonce, aad, input, output).map_err(code)
+    })
+}
+
+
+#[allow(clippy::too_many_arguments)]
+unsafe fn aead<AE: AeadCipher>(
+    seal: bool,
+    key: *const u8, key_len: usize,
+    nonce: *const u8, nonce_len: usize,
+    aad: *const u8, aad_len: usize,
+    input: *const u8, input_len: usize,
+    output: *mut u8, output_len: usize
+) -> c_int {
+    status(|| {
+        let key = self::input(key, key_len)?;
+        let nonce = self::input(nonce, nonce_len)?;
+        let aad = self::input(aad, aad_len)?;
+        let input = self::input(input, input_len)?;
+        let output = self::output(output, output_len)?;
+        if key.len() != AE::KEY_LENGTH || nonce.len() != AE::NONCE_LENGTH {
+            return Err(SARKARA_ERROR_LENGTH);
+        }
+
+        let ae = AE::new(key);
+        if seal {
+            ae.seal(nonce, aad, input, output)
+        } else {
+            ae.open(nonce, aad, input, output)
+        }.map_err(code)
+    })
+}
+
+fn code(err: Error) -> c_int {
+    match err {
+        Error::Length(_) => SARKARA_ERROR_LENGTH,
+        Error::Decoding(_) => SARKARA_ERROR_DECODING,
+        Error::VerificationFailed => SARKARA_ERROR_VERIFICATION_FAILED,
+        Error::InvalidSignature => SARKARA_ERROR_INVALID_SIGNATURE,
+        Error::KeyMismatch => SARKARA_ERROR_KEY_MISMATCH,
+        Error::WrongPassphrase => SARKARA_ERROR_WRONG_PASSPHRASE,
+        Error::InvalidState => SARKARA_ERROR_INVALID_STATE
+    }
+}
+
+fn status<F: FnOnce() -> Result<(), c_int>>(f: F) -> c_int {
+    // unwinding into C is undefined
+    match panic::catch_unwind(panic::AssertUnwindSafe(f)) {
+        Ok(Ok(())) => SARKARA_OK,
+        Ok(Err(err)) => err,
+        Err(_) => SARKARA_ERROR_PANIC
+    }
+}
+
+fn rng() -> Result<NorxRng, c_int> {
+    NorxRng::new().map_err(|_| SARKARA_ERROR_RNG)
+}
+
+unsafe fn input<'a>(ptr: *const u8, len: usize) -> Result<&'a [u8], c_int> {
+    match (ptr.is_null(), len) {
+        (true, 0) => Ok(&[]),
+        (true, _) => Err(SARKARA_ERROR_NULL_POINTER),
+        (false, _) => Ok(slice::from_raw_parts(ptr, len))
+    }
+}
+
+unsafe fn output<'a>(ptr: *mut u8, len: usize) -> Result<&'a mut [u8], c_int> {
+    match (ptr.is_null(), len) {
+        (true, 0) => Ok(&mut []),
+        (true, _) => Err(SARKARA_ERROR_NULL_POINTER),
+        (false, _) => Ok(slice::from_raw_parts_mut(ptr, len))
+    }
+}
+
+fn check<T: Packing>(buf: &[u8]) -> Result<(), c_int> {
+    if buf.len() == T::BYTES_LENGTH {
+        Ok(())
+    } else {
+        Err(SARKARA_ERROR_LENGTH)
+    }
+}
+
+fn read<T: Packing>(buf: &[u8]) -> Result<T, c_int> {
+    check::<T>(buf)?;
+    Ok(T::from_bytes(buf))
+}
+
+fn write<T: Packing>(t: &T, buf: &mut [u8]) -> Result<(), c_int> {
+    check::<T>(buf)?;
+    t.read_bytes(|bytes| buf.copy_from_slice(bytes));
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use core::fmt;
+use alloc::format;
+use alloc::vec::Vec;
+use alloc::string::String;
+use crate::hash::{ Xof, XofReader };
+use crate::hash::norx::NorxHash;
+use crate::armor::Armored;
+use crate::common::eq_ct;
+
+mod words;
+
+
+pub const FINGERPRINT_LENGTH: usize = 32;
+pub const SAS_LENGTH: usize = 4;
+
+const FINGERPRINT: u8 = 0x09;
+
+/// A stable identifier of a key, hash of its algorithm label and encoding.
+pub struct Fingerprint([u8; FINGERPRINT_LENGTH]);
+
+/// Short authentication string, compare it out of band to detect a MITM.
+pub struct ShortAuthString([u8; SAS_LENGTH]);
+
+impl Fingerprint {
+    pub fn of<T: Armored>(t: &T) -> Fingerprint {
+        let mut hasher = NorxHash::with_domain(FINGERPRINT);
+        hasher.update_framed(T::LABEL.as_bytes());
+        t.read_bytes(|bytes| hasher.update_framed(bytes));
+
+        let mut output = [0; FINGERPRINT_LENGTH];
+        hasher.xof().read(&mut output);
+        Fingerprint(output)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; FINGERPRINT_LENGTH] {
+        &self.0
+    }
+
+    pub fn to_hex(&self) -> String {
+        to_hex(&self.0)
+    }
+
+    /// Render as words, one word per byte.
+    pub fn to_words(&self) -> String {
+        to_words(&self.0)
+    }
+}
+
+impl ShortAuthString {
+    pub(crate) fn new(sas: [u8; SAS_LENGTH]) -> ShortAuthString {
+        ShortAuthString(sas)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; SAS_LENGTH] {
+        &self.0
+    }
+
+    /// Render as six decimal digits.
+    pub fn to_digits(&self) -> String {
+        format!("{:06}", u32::from_le_bytes(self.0) % 1_000_000)
+    }
+
+    /// Render as words, one word per byte.
+    pub fn to_words(&self) -> String {
+        to_words(&self.0)
+    }
+}
+
+impl PartialEq for Fingerprint {
+    fn eq(&self, rhs: &Fingerprint) -> bool {
+        eq_ct(&self.0, &rhs.0)
+    }
+}
+
+impl Eq for Fingerprint {}
+
+impl PartialEq for ShortAuthString {
+    fn eq(&self, rhs: &ShortAuthString) -> bool {
+        eq_ct(&self.0, &rhs.0)
+    }
+}
+
+impl Eq for ShortAuthString {}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+impl fmt::Debug for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Fingerprint({})", self.to_hex())
+    }
+}
+
+impl fmt::Display for ShortAuthString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_digits())
+    }
+}
+
+impl fmt::Debug for ShortAuthString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ShortAuthString({})", self.to_digits())
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn to_words(bytes: &[u8]) -> String {
+    bytes.iter()
+        .map(|&b| words::WORDS[usize::from(b)])
+        .collect::<Vec<_>>()
+        .join(" ")
+}
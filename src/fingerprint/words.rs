@@ -0,0 +1,36 @@
+// One word per byte value, sorted so the list is easy to audit.
+
+pub const WORDS: [&str; 256] = [
+    "acid", "acorn", "actor", "adult", "agent", "alarm", "album", "alley",
+    "amber", "angle", "ankle", "apple", "apron", "arena", "armor", "arrow",
+    "aspen", "atlas", "attic", "audio", "aunt", "award", "axis", "bacon",
+    "badge", "bagel", "baker", "bamboo", "banjo", "barn", "basil", "basin",
+    "beach", "beard", "beast", "bench", "berry", "bison", "blade", "blank",
+    "blaze", "bloom", "board", "boat", "bonus", "boot", "bottle", "bowl",
+    "brain", "brass", "bread", "brick", "bride", "broom", "brush", "bucket",
+    "bugle", "cabin", "cable", "cactus", "camel", "candle", "canoe", "canvas",
+    "canyon", "carbon", "cargo", "carpet", "castle", "cedar", "cello", "chair",
+    "chalk", "cherry", "chess", "chief", "cider", "circle", "clay", "cliff",
+    "clock", "cloud", "clover", "coast", "cobra", "cocoa", "comet", "coral",
+    "cotton", "cousin", "crab", "crane", "cream", "crown", "cube", "daisy",
+    "dancer", "delta", "denim", "desert", "dinner", "disco", "dragon", "drum",
+    "eagle", "earth", "echo", "elbow", "ember", "falcon", "fence", "ferry",
+    "fiber", "fig", "finch", "flag", "flame", "flute", "forest", "fossil",
+    "fox", "frost", "galaxy", "garden", "garlic", "gecko", "giant", "ginger",
+    "glove", "goat", "gold", "grape", "gravel", "guitar", "hammer", "harbor",
+    "harp", "hazel", "helmet", "heron", "honey", "hotel", "igloo", "island",
+    "ivory", "jacket", "jaguar", "jelly", "jewel", "jungle", "kayak", "kettle",
+    "kiwi", "koala", "ladder", "lagoon", "lamp", "lemon", "lizard", "locket",
+    "lotus", "magnet", "mango", "maple", "marble", "meadow", "melon", "meteor",
+    "mirror", "monkey", "moose", "motor", "muffin", "nectar", "needle", "nickel",
+    "oasis", "ocean", "olive", "onion", "orbit", "orchid", "otter", "oven",
+    "owl", "oyster", "paddle", "palace", "panda", "parrot", "peach", "pebble",
+    "pepper", "piano", "pigeon", "pilot", "pine", "planet", "plum", "pocket",
+    "polar", "pony", "poppy", "prism", "puzzle", "quartz", "quill", "rabbit",
+    "radar", "radio", "raven", "ribbon", "river", "robin", "rocket", "ruby",
+    "saddle", "salmon", "satin", "scarf", "shadow", "shark", "shell", "silver",
+    "sled", "snail", "spider", "squid", "stamp", "stone", "storm", "sugar",
+    "sunset", "swan", "table", "tango", "temple", "tiger", "toast", "tomato",
+    "topaz", "torch", "tower", "tulip", "tunnel", "turtle", "valley", "violin",
+    "wagon", "walnut", "whale", "willow", "window", "wizard", "yacht", "zebra"
+];
@@ -0,0 +1,334 @@
+//! KEM based handshakes in the style of PQNoise.
+//!
+//! Diffie-Hellman tokens are replaced by KEM tokens: `ekem` encapsulates
+//! to the remote ephemeral key, `skem` to the remote static key.
+//! Every token and payload is mixed into the transcript hash, which is the
+//! associated data of every handshake ciphertext.
+//!
+//! ```text
+//! IK:  <- s
+//!      ...
+//!      -> e, skem, s
+//!      <- ekem, skem
+//!
+//! XX:  -> e
+//!      <- ekem, s
+//!      -> skem, s
+//!      <- skem
+//! ```
+
+mod symmetric;
+
+use alloc::vec;
+use alloc::vec::Vec;
+use rand::{ Rng, CryptoRng };
+use seckey::TempKey;
+use crate::aead::AeadCipher;
+use crate::kex::{ KeyExchange, CheckedExchange };
+use crate::{ Packing, Error, Length };
+use self::symmetric::{ SymmetricState, nonce };
+
+pub use self::symmetric::HASH_LENGTH;
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pattern {
+    /// The initiator knows the responder static key, two messages.
+    IK,
+    /// Both static keys are transmitted, four messages.
+    XX,
+}
+
+#[derive(Clone, Copy)]
+enum Token {
+    E,
+    Ekem,
+    S,
+    Skem,
+}
+
+impl Pattern {
+    fn name(self) -> &'static [u8] {
+        match self {
+            Pattern::IK => b"sarkara handshake pqIK",
+            Pattern::XX => b"sarkara handshake pqXX"
+        }
+    }
+
+    fn messages(self) -> &'static [&'static [Token]] {
+        match self {
+            Pattern::IK => &[
+                &[Token::E, Token::Skem, Token::S],
+                &[Token::Ekem, Token::Skem]
+            ],
+            Pattern::XX => &[
+                &[Token::E],
+                &[Token::Ekem, Token::S],
+                &[Token::Skem, Token::S],
+                &[Token::Skem]
+            ]
+        }
+    }
+}
+
+
+pub struct HandshakeState<KEX: KeyExchange, AE: AeadCipher> {
+    symmetric: SymmetricState<AE>,
+    pattern: Pattern,
+    initiator: bool,
+    step: usize,
+    failed: bool,
+    s: (KEX::PrivateKey, KEX::PublicKey),
+    e: Option<(KEX::PrivateKey, KEX::PublicKey)>,
+    rs: Option<KEX::PublicKey>,
+    re: Option<KEX::PublicKey>
+}
+
+/// Directional keys after a finished handshake.
+pub struct Transport<AE: AeadCipher> {
+    send: AE,
+    recv: AE,
+    send_n: u64,
+    recv_n: u64,
+    h: [u8; HASH_LENGTH]
+}
+
+
+impl<KEX, AE> HandshakeState<KEX, AE>
+    where
+        KEX: CheckedExchange,
+        AE: AeadCipher
+{
+    /// `rs` is the responder static key, required by `IK`.
+    pub fn initiator(
+        pattern: Pattern,
+        s: (KEX::PrivateKey, KEX::PublicKey),
+        rs: Option<KEX::PublicKey>,
+        prologue: &[u8]
+    ) -> Result<Self, Error> {
+        let mut state = Self::new(pattern, true, s, prologue);
+
+        if pattern == Pattern::IK {
+            let rs = rs.ok_or(Error::InvalidState)?;
+            rs.read_bytes(|rs| state.symmetric.mix_hash(rs));
+            state.rs = Some(rs);
+        }
+
+        Ok(state)
+    }
+
+    pub fn responder(
+        pattern: Pattern,
+        s: (KEX::PrivateKey, KEX::PublicKey),
+        prologue: &[u8]
+    ) -> Self {
+        let mut state = Self::new(pattern, false, s, prologue);
+
+        if pattern == Pattern::IK {
+            let HandshakeState { symmetric, s, .. } = &mut state;
+            s.1.read_bytes(|s| symmetric.mix_hash(s));
+        }
+
+        state
+    }
+
+    fn new(pattern: Pattern, initiator: bool, s: (KEX::PrivateKey, KEX::PublicKey), prologue: &[u8]) -> Self {
+        let mut symmetric = SymmetricState::new(pattern.name());
+        symmetric.mix_hash(prologue);
+
+        HandshakeState {
+            symmetric, pattern, initiator,
+            step: 0, failed: false,
+            s, e: None, rs: None, re: None
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.step == self.pattern.messages().len()
+    }
+
+    /// Whether the next message is ours to write.
+    pub fn is_my_turn(&self) -> bool {
+        !self.is_finished() && (self.step % 2 == 0) == self.initiator
+    }
+
+    /// The remote static key, once received.
+    pub fn remote_static(&self) -> Option<&KEX::PublicKey> {
+        self.rs.as_ref()
+    }
+
+    /// Binds the whole transcript, e.g. for channel binding.
+    pub fn handshake_hash(&self) -> &[u8; HASH_LENGTH] {
+        self.symmetric.handshake_hash()
+    }
+
+    pub fn write_message<R: Rng + CryptoRng>(&mut self, r: R, payload: &[u8]) -> Result<Vec<u8>, Error> {
+        if self.failed || !self.is_my_turn() {
+            return Err(Error::InvalidState);
+        }
+
+        let result = self.write_tokens(r, payload);
+        self.advance(&result);
+        result
+    }
+
+    pub fn read_message(&mut self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        if self.failed || self.is_finished() || self.is_my_turn() {
+            return Err(Error::InvalidState);
+        }
+
+        let result = self.read_tokens(message);
+        self.advance(&result);
+        result
+    }
+
+    pub fn into_transport(self) -> Result<Transport<AE>, Error> {
+        if self.failed || !self.is_finished() {
+            return Err(Error::InvalidState);
+        }
+
+        let (k1, k2) = self.symmetric.split();
+        let (send, recv) = if self.initiator { (k1, k2) } else { (k2, k1) };
+
+        Ok(Transport {
+            send, recv,
+            send_n: 0, recv_n: 0,
+            h: *self.symmetric.handshake_hash()
+        })
+    }
+
+    fn advance<T>(&mut self, result: &Result<T, Error>) {
+        if result.is_ok() {
+            self.step += 1;
+        } else {
+            self.failed = true;
+        }
+    }
+
+    fn write_tokens<R: Rng + CryptoRng>(&mut self, mut r: R, payload: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut output = Vec::new();
+
+        for token in self.pattern.messages()[self.step] {
+            match token {
+                Token::E => {
+                    let (sk, pk) = KEX::keypair(&mut r);
+                    pk.read_bytes(|pk| {
+                        output.extend_from_slice(pk);
+                        self.symmetric.mix_hash(pk);
+                    });
+                    self.e = Some((sk, pk));
+                },
+                Token::Ekem => {
+                    let re = self.re.as_ref().ok_or(Error::InvalidState)?;
+                    encapsulate::<KEX, AE, _>(&mut r, &mut self.symmetric, re, &mut output);
+                },
+                Token::Skem => {
+                    let rs = self.rs.as_ref().ok_or(Error::InvalidState)?;
+                    encapsulate::<KEX, AE, _>(&mut r, &mut self.symmetric, rs, &mut output);
+                },
+                Token::S => {
+                    let HandshakeState { symmetric, s, .. } = self;
+                    s.1.read_bytes(|s| symmetric.encrypt_and_hash(s, &mut output))?;
+                }
+            }
+        }
+
+        self.symmetric.encrypt_and_hash(payload, &mut output)?;
+        Ok(output)
+    }
+
+    fn read_tokens(&mut self, mut message: &[u8]) -> Result<Vec<u8>, Error> {
+        for token in self.pattern.messages()[self.step] {
+            match token {
+                Token::E => {
+                    let pk = take(&mut message, KEX::PublicKey::BYTES_LENGTH)?;
+                    self.symmetric.mix_hash(pk);
+                    self.re = Some(KEX::PublicKey::from_bytes(pk));
+                },
+                Token::Ekem => {
+                    let (sk, _) = self.e.as_ref().ok_or(Error::InvalidState)?;
+                    let m = take(&mut message, KEX::Message::BYTES_LENGTH)?;
+                    decapsulate::<KEX, AE>(&mut self.symmetric, sk, m)?;
+                },
+                Token::Skem => {
+                    let m = take(&mut message, KEX::Message::BYTES_LENGTH)?;
+                    decapsulate::<KEX, AE>(&mut self.symmetric, &self.s.0, m)?;
+                },
+                Token::S => {
+                    let len = KEX::PublicKey::BYTES_LENGTH
+                        + if self.symmetric.has_key() { AE::TAG_LENGTH } else { 0 };
+                    let ciphertext = take(&mut message, len)?;
+                    let mut pk = Vec::with_capacity(KEX::PublicKey::BYTES_LENGTH);
+                    self.symmetric.decrypt_and_hash(ciphertext, &mut pk)?;
+                    self.rs = Some(KEX::PublicKey::from_bytes(&pk));
+                }
+            }
+        }
+
+        let mut payload = Vec::new();
+        self.symmetric.decrypt_and_hash(message, &mut payload)?;
+        Ok(payload)
+    }
+}
+
+impl<AE: AeadCipher> Transport<AE> {
+    pub fn handshake_hash(&self) -> &[u8; HASH_LENGTH] {
+        &self.h
+    }
+
+    /// `output` must be `input.len() + AE::TAG_LENGTH` long.
+    pub fn seal(&mut self, input: &[u8], output: &mut [u8]) -> Result<(), Error> {
+        self.send.seal(&nonce::<AE>(self.send_n)?, &[], input, output)?;
+        self.send_n += 1;
+        Ok(())
+    }
+
+    /// Messages must be opened in the order they were sealed.
+    pub fn open(&mut self, input: &[u8], output: &mut [u8]) -> Result<(), Error> {
+        self.recv.open(&nonce::<AE>(self.recv_n)?, &[], input, output)?;
+        self.recv_n += 1;
+        Ok(())
+    }
+}
+
+
+fn encapsulate<KEX, AE, R>(r: R, symmetric: &mut SymmetricState<AE>, pk: &KEX::PublicKey, output: &mut Vec<u8>)
+    where
+        KEX: KeyExchange,
+        AE: AeadCipher,
+        R: Rng + CryptoRng
+{
+    let mut sharedkey: Vec<u8> = vec![0; KEX::SHARED_LENGTH];
+    let mut sharedkey = TempKey::from(&mut sharedkey as &mut [u8]);
+
+    let m = KEX::exchange_to(r, &mut sharedkey, pk);
+    m.read_bytes(|m| {
+        output.extend_from_slice(m);
+        symmetric.mix_hash(m);
+    });
+    symmetric.mix_key(&sharedkey);
+}
+
+fn decapsulate<KEX, AE>(symmetric: &mut SymmetricState<AE>, sk: &KEX::PrivateKey, m: &[u8]) -> Result<(), Error>
+    where
+        KEX: CheckedExchange,
+        AE: AeadCipher
+{
+    let mut sharedkey: Vec<u8> = vec![0; KEX::SHARED_LENGTH];
+    let mut sharedkey = TempKey::from(&mut sharedkey as &mut [u8]);
+
+    symmetric.mix_hash(m);
+    <KEX as CheckedExchange>::exchange_from(&mut sharedkey, sk, &KEX::Message::from_bytes(m))?;
+    symmetric.mix_key(&sharedkey);
+    Ok(())
+}
+
+fn take<'a>(message: &mut &'a [u8], len: usize) -> Result<&'a [u8], Error> {
+    if message.len() < len {
+        return Err(Error::Length(Length::Encoding));
+    }
+
+    let (head, tail) = message.split_at(len);
+    *message = tail;
+    Ok(head)
+}
@@ -0,0 +1,122 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use crate::aead::AeadCipher;
+use crate::hash::{ Xof, XofReader };
+use crate::hash::norx::NorxHash;
+use crate::kdf::{ Kdf, norx::NorxKdf };
+use crate::common::zero;
+use crate::{ Error, Length };
+
+
+pub const HASH_LENGTH: usize = 32;
+
+const HANDSHAKE: u8 = 0x0a;
+
+/// Transcript hash, chaining key and the current AEAD key.
+pub struct SymmetricState<AE: AeadCipher> {
+    h: [u8; HASH_LENGTH],
+    ck: [u8; HASH_LENGTH],
+    k: Option<AE>,
+    n: u64
+}
+
+impl<AE: AeadCipher> SymmetricState<AE> {
+    pub fn new(protocol: &[u8]) -> Self {
+        let mut h = [0; HASH_LENGTH];
+        let mut hasher = NorxHash::with_domain(HANDSHAKE);
+        hasher.update_framed(protocol);
+        hasher.xof().read(&mut h);
+
+        SymmetricState { h, ck: h, k: None, n: 0 }
+    }
+
+    pub fn mix_hash(&mut self, data: &[u8]) {
+        let mut hasher = NorxHash::with_domain(HANDSHAKE);
+        hasher.update_framed(&self.h);
+        hasher.update_framed(data);
+        hasher.xof().read(&mut self.h);
+    }
+
+    pub fn mix_key(&mut self, ikm: &[u8]) {
+        let mut key = vec![0; AE::KEY_LENGTH];
+        let kdf = NorxKdf::extract(&self.ck, ikm);
+        kdf.expand(b"ck", &mut self.ck);
+        kdf.expand(b"k", &mut key);
+
+        self.k = Some(AE::new(&key));
+        self.n = 0;
+        zero(&mut key);
+    }
+
+    pub fn has_key(&self) -> bool {
+        self.k.is_some()
+    }
+
+    pub fn encrypt_and_hash(&mut self, input: &[u8], output: &mut Vec<u8>) -> Result<(), Error> {
+        let start = output.len();
+
+        if let Some(k) = self.k.as_ref() {
+            output.resize(start + input.len() + AE::TAG_LENGTH, 0);
+            k.seal(&nonce::<AE>(self.n)?, &self.h, input, &mut output[start..])?;
+            self.n += 1;
+        } else {
+            output.extend_from_slice(input);
+        }
+
+        let h = output[start..].to_vec();
+        self.mix_hash(&h);
+        Ok(())
+    }
+
+    pub fn decrypt_and_hash(&mut self, input: &[u8], output: &mut Vec<u8>) -> Result<(), Error> {
+        let start = output.len();
+
+        if let Some(k) = self.k.as_ref() {
+            let len = input.len().checked_sub(AE::TAG_LENGTH)
+                .ok_or(Error::Length(Length::Encoding))?;
+            output.resize(start + len, 0);
+            k.open(&nonce::<AE>(self.n)?, &self.h, input, &mut output[start..])?;
+            self.n += 1;
+        } else {
+            output.extend_from_slice(input);
+        }
+
+        self.mix_hash(input);
+        Ok(())
+    }
+
+    pub fn handshake_hash(&self) -> &[u8; HASH_LENGTH] {
+        &self.h
+    }
+
+    /// Returns the initiator to responder and the responder to initiator keys.
+    pub fn split(&self) -> (AE, AE) {
+        let mut k1 = vec![0; AE::KEY_LENGTH];
+        let mut k2 = vec![0; AE::KEY_LENGTH];
+        let kdf = NorxKdf::extract(&self.ck, &[]);
+        kdf.expand(b"initiator", &mut k1);
+        kdf.expand(b"responder", &mut k2);
+
+        let keys = (AE::new(&k1), AE::new(&k2));
+        zero(&mut k1);
+        zero(&mut k2);
+        keys
+    }
+}
+
+impl<AE: AeadCipher> Drop for SymmetricState<AE> {
+    fn drop(&mut self) {
+        zero(&mut self.ck);
+    }
+}
+
+/// The counter in little endian, zero padded to the nonce length.
+pub fn nonce<AE: AeadCipher>(n: u64) -> Result<Vec<u8>, Error> {
+    if n == u64::MAX {
+        return Err(Error::InvalidState);
+    }
+
+    let mut nonce = vec![0; AE::NONCE_LENGTH];
+    nonce[..8].copy_from_slice(&n.to_le_bytes());
+    Ok(nonce)
+}
@@ -0,0 +1,28 @@
+pub mod norx;
+
+
+pub trait Hash: Sized {
+    const OUTPUT_LENGTH: usize;
+
+    fn new() -> Self;
+    fn update(&mut self, data: &[u8]);
+
+    /// TODO should be `output: &mut [u8; Self::OUTPUT_LENGTH]`
+    fn finalize(self, output: &mut [u8]);
+
+    fn digest(data: &[u8], output: &mut [u8]) {
+        let mut hasher = Self::new();
+        hasher.update(data);
+        hasher.finalize(output)
+    }
+}
+
+pub trait Xof: Hash {
+    type Reader: XofReader;
+
+    fn xof(self) -> Self::Reader;
+}
+
+pub trait XofReader {
+    fn read(&mut self, output: &mut [u8]);
+}
@@ -0,0 +1,111 @@
+#[cfg(feature = "std")]
+use std::io;
+use arrayref::array_mut_ref;
+use norx_permutation::{ U, S, norx };
+use crate::common::zero;
+use super::{ Hash, Xof, XofReader };
+
+
+pub const OUTPUT_LENGTH: usize = 32;
+
+const RATE: usize = 12 * 8;
+const HASH: u8 = 0x01;
+
+#[derive(Clone)]
+pub struct NorxHash {
+    state: [U; S],
+    pos: usize
+}
+
+pub struct NorxReader(NorxHash);
+
+impl NorxHash {
+    pub(crate) fn with_domain(domain: u8) -> NorxHash {
+        let mut state = [0; S];
+        state[S - 1] = U::from(domain);
+        norx(&mut state);
+        NorxHash { state, pos: 0 }
+    }
+
+    pub(crate) fn update_framed(&mut self, data: &[u8]) {
+        self.update(&(data.len() as u64).to_le_bytes());
+        self.update(data);
+    }
+
+    fn pad(&mut self) {
+        self.state[self.pos / 8] ^= 0x01 << (8 * (self.pos % 8));
+        self.state[(RATE - 1) / 8] ^= 0x80 << (8 * ((RATE - 1) % 8));
+        norx(&mut self.state);
+        self.pos = 0;
+    }
+
+    fn squeeze(&mut self, output: &mut [u8]) {
+        for b in output {
+            if self.pos == RATE {
+                norx(&mut self.state);
+                self.pos = 0;
+            }
+
+            *b = (self.state[self.pos / 8] >> (8 * (self.pos % 8))) as u8;
+            self.pos += 1;
+        }
+    }
+}
+
+impl Hash for NorxHash {
+    const OUTPUT_LENGTH: usize = OUTPUT_LENGTH;
+
+    fn new() -> Self {
+        NorxHash::with_domain(HASH)
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        for &b in data {
+            self.state[self.pos / 8] ^= U::from(b) << (8 * (self.pos % 8));
+            self.pos += 1;
+
+            if self.pos == RATE {
+                norx(&mut self.state);
+                self.pos = 0;
+            }
+        }
+    }
+
+    fn finalize(self, output: &mut [u8]) {
+        let output = array_mut_ref!(output, 0, OUTPUT_LENGTH);
+        self.xof().read(output);
+    }
+}
+
+impl Xof for NorxHash {
+    type Reader = NorxReader;
+
+    fn xof(mut self) -> Self::Reader {
+        self.pad();
+        NorxReader(self)
+    }
+}
+
+impl XofReader for NorxReader {
+    fn read(&mut self, output: &mut [u8]) {
+        self.0.squeeze(output)
+    }
+}
+
+#[cfg(feature = "std")]
+impl io::Write for NorxHash {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for NorxHash {
+    fn drop(&mut self) {
+        zero(&mut self.state);
+    }
+}
@@ -0,0 +1,236 @@
+//! Hybrid public key encryption in the style of RFC 9180.
+//!
+//! The key schedule follows the RFC with `NorxKdf` as KDF,
+//! it is not interoperable with other HPKE implementations.
+//!
+//! Only the `Base` and `Psk` modes are provided. Kyber cannot authenticate
+//! the sender without interaction, sign the encapsulated key instead.
+
+use core::marker::PhantomData;
+use alloc::vec;
+use alloc::vec::Vec;
+use rand::{ Rng, CryptoRng };
+use seckey::TempKey;
+use crate::aead::AeadCipher;
+use crate::kex::KeyExchange;
+use crate::kdf::{ Kdf, norx::{ NorxKdf, PRK_LENGTH } };
+use crate::registry::Identified;
+use crate::common::zero;
+use crate::{ Packing, Error, Length };
+
+
+/// Minimum PSK length, the PSK must have at least this much entropy.
+pub const MIN_PSK_LENGTH: usize = 32;
+
+const VERSION: &[u8] = b"HPKE-v1";
+const SUITE: &[u8] = b"SARKARA";
+
+/// TODO `Auth` and `AuthPsk` need an authenticated KEM, which Kyber does not provide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Base = 0,
+    Psk = 1
+}
+
+pub struct Hpke<KEX, AE>(PhantomData<(KEX, AE)>);
+
+/// Encryption context, use it in one direction only.
+pub struct Context<AE: AeadCipher> {
+    suite: Vec<u8>,
+    aead: AE,
+    base_nonce: Vec<u8>,
+    exporter: NorxKdf,
+    seq: u64
+}
+
+
+impl<KEX, AE> Hpke<KEX, AE>
+    where
+        KEX: KeyExchange + Identified,
+        AE: AeadCipher + Identified
+{
+    /// `psk` is `(psk, psk_id)` and selects the `Psk` mode.
+    pub fn setup_sender<R: Rng + CryptoRng>(
+        r: R,
+        pk: &KEX::PublicKey,
+        info: &[u8],
+        psk: Option<(&[u8], &[u8])>
+    ) -> Result<(KEX::Message, Context<AE>), Error> {
+        let mut sharedkey: Vec<u8> = vec![0; KEX::SHARED_LENGTH];
+        let mut sharedkey = TempKey::from(&mut sharedkey as &mut [u8]);
+
+        let m = KEX::exchange_to(r, &mut sharedkey, pk);
+        let mode = if psk.is_some() { Mode::Psk } else { Mode::Base };
+        let ctx = Self::setup(mode, &sharedkey, &m, pk, info, psk)?;
+
+        Ok((m, ctx))
+    }
+
+    pub fn setup_receiver(
+        sk: &KEX::PrivateKey,
+        m: &KEX::Message,
+        info: &[u8],
+        psk: Option<(&[u8], &[u8])>
+    ) -> Result<Context<AE>, Error> {
+        let mut sharedkey: Vec<u8> = vec![0; KEX::SHARED_LENGTH];
+        let mut sharedkey = TempKey::from(&mut sharedkey as &mut [u8]);
+
+        KEX::exchange_from(&mut sharedkey, sk, m);
+        let mode = if psk.is_some() { Mode::Psk } else { Mode::Base };
+        Self::setup(mode, &sharedkey, m, &KEX::public_key(sk), info, psk)
+    }
+
+    /// Derive a context from a shared secret directly.
+    pub fn key_schedule(
+        mode: Mode,
+        shared_secret: &[u8],
+        info: &[u8],
+        psk: Option<(&[u8], &[u8])>
+    ) -> Result<Context<AE>, Error> {
+        let suite = suite_id::<KEX, AE>();
+
+        let (psk, psk_id) = match (mode, psk) {
+            (Mode::Base, None) => (&[][..], &[][..]),
+            (Mode::Psk, Some((psk, psk_id))) => {
+                if psk.len() < MIN_PSK_LENGTH || psk_id.is_empty() {
+                    return Err(Error::Length(Length::Key));
+                }
+                (psk, psk_id)
+            },
+            _ => return Err(Error::InvalidState)
+        };
+
+        let mut context = Vec::with_capacity(1 + 2 * PRK_LENGTH);
+        context.push(mode as u8);
+        context.extend_from_slice(labeled_extract(&suite, &[], b"psk_id_hash", psk_id).prk());
+        context.extend_from_slice(labeled_extract(&suite, &[], b"info_hash", info).prk());
+
+        let secret = labeled_extract(&suite, shared_secret, b"secret", psk);
+
+        let mut key: Vec<u8> = vec![0; AE::KEY_LENGTH];
+        let mut key = TempKey::from(&mut key as &mut [u8]);
+        let mut base_nonce = vec![0; AE::NONCE_LENGTH];
+        let mut exporter = [0; PRK_LENGTH];
+        labeled_expand(&suite, &secret, b"key", &context, &mut key)?;
+        labeled_expand(&suite, &secret, b"base_nonce", &context, &mut base_nonce)?;
+        labeled_expand(&suite, &secret, b"exp", &context, &mut exporter)?;
+
+        let exporter_kdf = NorxKdf::from_prk(&exporter);
+        zero(&mut exporter);
+
+        Ok(Context {
+            suite,
+            aead: AE::new(&key),
+            base_nonce,
+            exporter: exporter_kdf,
+            seq: 0
+        })
+    }
+
+    fn setup(
+        mode: Mode,
+        sharedkey: &[u8],
+        m: &KEX::Message,
+        pk: &KEX::PublicKey,
+        info: &[u8],
+        psk: Option<(&[u8], &[u8])>
+    ) -> Result<Context<AE>, Error> {
+        let suite = kem_suite_id::<KEX>();
+
+        // kem_context = enc || pkR
+        let mut kem_context = Vec::with_capacity(KEX::Message::BYTES_LENGTH + KEX::PublicKey::BYTES_LENGTH);
+        m.read_bytes(|m| kem_context.extend_from_slice(m));
+        pk.read_bytes(|pk| kem_context.extend_from_slice(pk));
+
+        let mut shared_secret = [0; PRK_LENGTH];
+        let eae_prk = labeled_extract(&suite, &[], b"eae_prk", sharedkey);
+        labeled_expand(&suite, &eae_prk, b"shared_secret", &kem_context, &mut shared_secret)?;
+
+        let ctx = Self::key_schedule(mode, &shared_secret, info, psk);
+        zero(&mut shared_secret);
+        ctx
+    }
+}
+
+impl<AE: AeadCipher> Context<AE> {
+    /// `output` must be `input.len() + AE::TAG_LENGTH` long.
+    pub fn seal(&mut self, aad: &[u8], input: &[u8], output: &mut [u8]) -> Result<(), Error> {
+        self.aead.seal(&self.nonce()?, aad, input, output)?;
+        self.seq += 1;
+        Ok(())
+    }
+
+    /// Messages must be opened in the order they were sealed.
+    pub fn open(&mut self, aad: &[u8], input: &[u8], output: &mut [u8]) -> Result<(), Error> {
+        self.aead.open(&self.nonce()?, aad, input, output)?;
+        self.seq += 1;
+        Ok(())
+    }
+
+    /// Derive a secret bound to this context, `len` is at most `u16::MAX`.
+    pub fn export(&self, context: &[u8], len: usize) -> Result<Vec<u8>, Error> {
+        let mut output = vec![0; len];
+        labeled_expand(&self.suite, &self.exporter, b"sec", context, &mut output)?;
+        Ok(output)
+    }
+
+    /// `base_nonce` xor the big endian sequence number.
+    fn nonce(&self) -> Result<Vec<u8>, Error> {
+        if self.seq == u64::MAX {
+            return Err(Error::InvalidState);
+        }
+
+        let mut nonce = self.base_nonce.clone();
+        let offset = nonce.len() - 8;
+        for (n, s) in nonce[offset..].iter_mut().zip(&self.seq.to_be_bytes()) {
+            *n ^= s;
+        }
+        Ok(nonce)
+    }
+}
+
+
+fn kem_suite_id<KEX: Identified>() -> Vec<u8> {
+    let mut suite = Vec::with_capacity(SUITE.len() + 5);
+    suite.extend_from_slice(SUITE);
+    suite.extend_from_slice(b"KEM");
+    suite.extend_from_slice(&KEX::ALGORITHM.id().to_be_bytes());
+    suite
+}
+
+fn suite_id<KEX: Identified, AE: Identified>() -> Vec<u8> {
+    let mut suite = Vec::with_capacity(SUITE.len() + 8);
+    suite.extend_from_slice(SUITE);
+    suite.extend_from_slice(b"HPKE");
+    suite.extend_from_slice(&KEX::ALGORITHM.id().to_be_bytes());
+    suite.extend_from_slice(&AE::ALGORITHM.id().to_be_bytes());
+    suite
+}
+
+fn labeled_extract(suite: &[u8], salt: &[u8], label: &[u8], ikm: &[u8]) -> NorxKdf {
+    let mut labeled_ikm = Vec::with_capacity(VERSION.len() + suite.len() + label.len() + ikm.len());
+    labeled_ikm.extend_from_slice(VERSION);
+    labeled_ikm.extend_from_slice(suite);
+    labeled_ikm.extend_from_slice(label);
+    labeled_ikm.extend_from_slice(ikm);
+
+    let kdf = NorxKdf::extract(salt, &labeled_ikm);
+    zero(&mut labeled_ikm);
+    kdf
+}
+
+fn labeled_expand(suite: &[u8], prk: &NorxKdf, label: &[u8], info: &[u8], output: &mut [u8]) -> Result<(), Error> {
+    if output.len() > u16::MAX as usize {
+        return Err(Error::Length(Length::Output));
+    }
+
+    let mut labeled_info = Vec::with_capacity(2 + VERSION.len() + suite.len() + label.len() + info.len());
+    labeled_info.extend_from_slice(&(output.len() as u16).to_be_bytes());
+    labeled_info.extend_from_slice(VERSION);
+    labeled_info.extend_from_slice(suite);
+    labeled_info.extend_from_slice(label);
+    labeled_info.extend_from_slice(info);
+
+    prk.expand(&labeled_info, output);
+    Ok(())
+}
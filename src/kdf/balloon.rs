@@ -0,0 +1,88 @@
+//! Balloon hashing, a memory-hard password hashing function.
+//!
+//! Boneh, Corrigan-Gibbs and Schechter, https://eprint.iacr.org/2016/027
+
+use alloc::vec;
+use crate::hash::{ Hash, Xof, XofReader };
+use crate::hash::norx::NorxHash;
+use crate::common::zero;
+
+
+const BLOCK_LENGTH: usize = 32;
+const DELTA: u64 = 3;
+const BALLOON: u8 = 0x08;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Params {
+    /// Number of 32 bytes blocks kept in memory.
+    pub space_cost: u32,
+    /// Number of rounds over the memory.
+    pub time_cost: u32
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        // 4 MiB
+        Params { space_cost: 1 << 17, time_cost: 3 }
+    }
+}
+
+pub fn balloon(params: &Params, password: &[u8], salt: &[u8], output: &mut [u8]) {
+    assert!(params.space_cost > 0 && params.time_cost > 0);
+
+    let space_cost = params.space_cost as usize;
+    let mut counter = 0;
+    let mut buf = vec![[0; BLOCK_LENGTH]; space_cost];
+
+    // expand
+    let mut hasher = block_hasher(&mut counter);
+    hasher.update_framed(password);
+    hasher.update_framed(salt);
+    hasher.xof().read(&mut buf[0]);
+    for m in 1..space_cost {
+        let mut hasher = block_hasher(&mut counter);
+        hasher.update(&buf[m - 1]);
+        hasher.xof().read(&mut buf[m]);
+    }
+
+    // mix
+    for t in 0..u64::from(params.time_cost) {
+        for m in 0..space_cost {
+            let prev = buf[(m + space_cost - 1) % space_cost];
+            let mut hasher = block_hasher(&mut counter);
+            hasher.update(&prev);
+            hasher.update(&buf[m]);
+            hasher.xof().read(&mut buf[m]);
+
+            for i in 0..DELTA {
+                let mut idx = [0; 8];
+                let mut hasher = block_hasher(&mut counter);
+                hasher.update_framed(salt);
+                hasher.update(&t.to_le_bytes());
+                hasher.update(&(m as u64).to_le_bytes());
+                hasher.update(&i.to_le_bytes());
+                hasher.xof().read(&mut idx);
+                let other = (u64::from_le_bytes(idx) % space_cost as u64) as usize;
+
+                let other = buf[other];
+                let mut hasher = block_hasher(&mut counter);
+                hasher.update(&buf[m]);
+                hasher.update(&other);
+                hasher.xof().read(&mut buf[m]);
+            }
+        }
+    }
+
+    let mut hasher = block_hasher(&mut counter);
+    hasher.update(&buf[space_cost - 1]);
+    hasher.xof().read(output);
+
+    zero(&mut buf);
+}
+
+fn block_hasher(counter: &mut u64) -> NorxHash {
+    let mut hasher = NorxHash::with_domain(BALLOON);
+    hasher.update(&counter.to_le_bytes());
+    *counter += 1;
+    hasher
+}
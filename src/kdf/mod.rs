@@ -0,0 +1,19 @@
+pub mod norx;
+#[cfg(feature = "alloc")]
+pub mod balloon;
+
+
+pub trait Kdf: Sized {
+    const PRK_LENGTH: usize;
+
+    fn extract(salt: &[u8], ikm: &[u8]) -> Self;
+
+    /// TODO should be `prk: &[u8; Self::PRK_LENGTH]`
+    fn from_prk(prk: &[u8]) -> Self;
+
+    fn expand(&self, info: &[u8], output: &mut [u8]);
+
+    fn derive(salt: &[u8], ikm: &[u8], info: &[u8], output: &mut [u8]) {
+        Self::extract(salt, ikm).expand(info, output)
+    }
+}
@@ -0,0 +1,53 @@
+use crate::hash::{ Hash, Xof, XofReader };
+use crate::hash::norx::NorxHash;
+use crate::common::zero;
+use super::Kdf;
+
+
+pub const PRK_LENGTH: usize = 32;
+
+const EXTRACT: u8 = 0x02;
+const EXPAND: u8 = 0x03;
+
+pub struct NorxKdf([u8; PRK_LENGTH]);
+
+impl NorxKdf {
+    #[cfg(feature = "alloc")]
+    pub(crate) fn prk(&self) -> &[u8; PRK_LENGTH] {
+        &self.0
+    }
+}
+
+impl Kdf for NorxKdf {
+    const PRK_LENGTH: usize = PRK_LENGTH;
+
+    fn extract(salt: &[u8], ikm: &[u8]) -> Self {
+        let mut hasher = NorxHash::with_domain(EXTRACT);
+        hasher.update_framed(salt);
+        hasher.update_framed(ikm);
+
+        let mut prk = [0; PRK_LENGTH];
+        hasher.xof().read(&mut prk);
+        NorxKdf(prk)
+    }
+
+    fn from_prk(prk: &[u8]) -> Self {
+        let mut k = [0; PRK_LENGTH];
+        k.copy_from_slice(prk);
+        NorxKdf(k)
+    }
+
+    fn expand(&self, info: &[u8], output: &mut [u8]) {
+        let mut hasher = NorxHash::with_domain(EXPAND);
+        hasher.update_framed(&self.0);
+        hasher.update_framed(info);
+        hasher.update(&(output.len() as u64).to_le_bytes());
+        hasher.xof().read(output);
+    }
+}
+
+impl Drop for NorxKdf {
+    fn drop(&mut self) {
+        zero(&mut self.0);
+    }
+}
@@ -1,12 +1,17 @@
-use arrayref::array_mut_ref;
-use rand::{ Rng, CryptoRng };
+use arrayref::{ array_ref, array_mut_ref };
+use rand::{ Rng, CryptoRng, SeedableRng };
 use kyber::{ params, kem };
-use crate::{ Packing, Error };
-use super::{ KeyExchange, CheckedExchange };
+use crate::kdf::{ Kdf, norx::NorxKdf };
+use crate::rand::norx::NorxRng;
+use crate::common::eq_ct;
+#[cfg(feature = "alloc")]
+use crate::pkcs8::AlgorithmIdentifier;
+use crate::{ Packing, Error, SEED_LENGTH };
+use super::{ KeyExchange, CheckedExchange, SeedableExchange };
 
 
 pub struct Kyber;
-pub struct PrivateKey([u8; params::SECRETKEYBYTES]);
+pub struct PrivateKey([u8; params::SECRETKEYBYTES], Option<[u8; SEED_LENGTH]>);
 pub struct PublicKey([u8; params::PUBLICKEYBYTES]);
 pub struct Message([u8; params::CIPHERTEXTBYTES]);
 
@@ -18,14 +23,12 @@ impl KeyExchange for Kyber {
     const SHARED_LENGTH: usize = params::SYMBYTES;
 
     fn keypair<R: Rng + CryptoRng>(mut r: R) -> (Self::PrivateKey, Self::PublicKey) {
-        let mut sk = [0; params::SECRETKEYBYTES];
-
-        let mut pk = [0; params::PUBLICKEYBYTES];
-        kem::keypair(&mut r, &mut pk, &mut sk);
-        (PrivateKey(sk), PublicKey(pk))
+        let mut seed = [0; SEED_LENGTH];
+        r.fill_bytes(&mut seed);
+        Kyber::keypair_from_seed(&seed)
     }
 
-    fn exchange_to<R: Rng + CryptoRng>(mut r: R, sharedkey: &mut [u8], &PublicKey(ref pk): &Self::PublicKey) -> Self::Message {
+    fn exchange_to<R: Rng + CryptoRng>(mut r: R, sharedkey: &mut [u8], PublicKey(pk): &Self::PublicKey) -> Self::Message {
         let sharedkey = array_mut_ref!(sharedkey, 0, params::SYMBYTES);
         let mut c = [0; params::CIPHERTEXTBYTES];
         kem::enc(&mut r, &mut c, sharedkey, pk);
@@ -35,13 +38,38 @@ impl KeyExchange for Kyber {
     fn exchange_from(sharedkey: &mut [u8], sk: &Self::PrivateKey, m: &Self::Message) {
         let _ = <Kyber as CheckedExchange>::exchange_from(sharedkey, sk, m);
     }
+
+    fn public_key(PrivateKey(sk, _): &Self::PrivateKey) -> Self::PublicKey {
+        // sk = indcpa_sk || pk || H(pk) || z
+        const OFFSET: usize = params::SECRETKEYBYTES - params::PUBLICKEYBYTES - 2 * params::SYMBYTES;
+
+        PublicKey(*array_ref!(sk, OFFSET, params::PUBLICKEYBYTES))
+    }
+
+    /// Also checks the secret part with an encapsulation round trip.
+    fn check_keypair(sk: &Self::PrivateKey, pk: &Self::PublicKey) -> Result<(), Error> {
+        if !eq_ct(&Kyber::public_key(sk).0, &pk.0) {
+            return Err(Error::KeyMismatch);
+        }
+
+        // the encapsulation randomness need not be secret here
+        let mut rng = NorxRng::from_seed([0; SEED_LENGTH]);
+        let mut a = [0; params::SYMBYTES];
+        let mut b = [0; params::SYMBYTES];
+        let m = Kyber::exchange_to(&mut rng, &mut a, pk);
+
+        match <Kyber as CheckedExchange>::exchange_from(&mut b, sk, &m) {
+            Ok(()) if eq_ct(&a, &b) => Ok(()),
+            _ => Err(Error::KeyMismatch)
+        }
+    }
 }
 
 impl CheckedExchange for Kyber {
     fn exchange_from(
         sharedkey: &mut [u8],
-        &PrivateKey(ref sk): &Self::PrivateKey,
-        &Message(ref m): &Self::Message
+        PrivateKey(sk, _): &Self::PrivateKey,
+        Message(m): &Self::Message
     ) -> Result<(), Error> {
         let sharedkey = array_mut_ref!(sharedkey, 0, params::SYMBYTES);
         if kem::dec(sharedkey, m, sk) {
@@ -52,16 +80,56 @@ impl CheckedExchange for Kyber {
     }
 }
 
+impl SeedableExchange for Kyber {
+    fn keypair_from_seed(seed: &[u8; SEED_LENGTH]) -> (Self::PrivateKey, Self::PublicKey) {
+        let mut sk = [0; params::SECRETKEYBYTES];
+        let mut pk = [0; params::PUBLICKEYBYTES];
+
+        // NOTE the keypair depends on how `kyber` consumes the rng,
+        // so the `kyber` version is pinned.
+        let mut rngseed = [0; SEED_LENGTH];
+        NorxKdf::derive(b"sarkara seed", seed, b"kyber keypair", &mut rngseed);
+        let mut rng = NorxRng::from_seed(rngseed);
+
+        kem::keypair(&mut rng, &mut pk, &mut sk);
+        (PrivateKey(sk, Some(*seed)), PublicKey(pk))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl AlgorithmIdentifier for Kyber {
+    type PrivateKey = PrivateKey;
+    type PublicKey = PublicKey;
+
+    /// 2.25.316991036999128169988469511729626057660.1, round 1 Kyber768
+    const OID: &'static [u8] = &[
+        0x69, 0x83, 0xdc, 0xfa, 0xa0, 0xb1, 0xc9, 0xe2, 0xb2, 0xb2, 0xb9, 0x94, 0x9e, 0xbf, 0x89, 0xd0, 0xde, 0xb6, 0x97, 0x3c,
+        0x01
+    ];
+}
+
+impl PrivateKey {
+    /// The seed this key was generated from, if known.
+    ///
+    /// Keys restored by `Packing::from_bytes` do not carry a seed.
+    pub fn seed(&self) -> Option<&[u8; SEED_LENGTH]> {
+        self.1.as_ref()
+    }
+}
+
 eq!(PrivateKey);
 eq!(PublicKey);
 eq!(Message);
-packing!(PrivateKey; params::SECRETKEYBYTES);
+packing!(PrivateKey; params::SECRETKEYBYTES; None);
 packing!(PublicKey; params::PUBLICKEYBYTES);
 packing!(Message; params::CIPHERTEXTBYTES);
+armored!(PrivateKey; "SARKARA KYBER PRIVATE KEY");
+armored!(PublicKey; "SARKARA KYBER PUBLIC KEY");
+armored!(Message; "SARKARA KYBER MESSAGE");
 
 #[cfg(feature = "serde")]
 mod serde1 {
-    use std::fmt;
+    use core::fmt;
     use serde::{
         Serialize, Serializer, Deserialize, Deserializer,
         de::{ self, Visitor }
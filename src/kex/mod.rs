@@ -1,5 +1,6 @@
 use rand::{ Rng, CryptoRng };
-use crate::{ Packing, Error };
+use crate::common::eq_ct;
+use crate::{ Packing, Error, SEED_LENGTH };
 
 pub mod kyber;
 
@@ -18,8 +19,25 @@ pub trait KeyExchange {
 
     /// TODO should be `sharedkey: &mut [u8; Self::SHARED_LENGTH]`
     fn exchange_from(sharedkey: &mut [u8], sk: &Self::PrivateKey, m: &Self::Message);
+
+    fn public_key(sk: &Self::PrivateKey) -> Self::PublicKey;
+
+    /// Check that the private key and public key belong together.
+    fn check_keypair(sk: &Self::PrivateKey, pk: &Self::PublicKey) -> Result<(), Error> {
+        let pk2 = Self::public_key(sk);
+        if pk.read_bytes(|x| pk2.read_bytes(|y| eq_ct(x, y))) {
+            Ok(())
+        } else {
+            Err(Error::KeyMismatch)
+        }
+    }
 }
 
 pub trait CheckedExchange: KeyExchange {
     fn exchange_from(sharedkey: &mut [u8], sk: &Self::PrivateKey, m: &Self::Message) -> Result<(), Error>;
 }
+
+pub trait SeedableExchange: KeyExchange {
+    /// Deterministically generate a keypair, the private key keeps the seed.
+    fn keypair_from_seed(seed: &[u8; SEED_LENGTH]) -> (Self::PrivateKey, Self::PublicKey);
+}
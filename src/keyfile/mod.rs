@@ -0,0 +1,172 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use rand::{ Rng, CryptoRng };
+use seckey::TempKey;
+use crate::aead::AeadCipher;
+use crate::aead::norx6441::Norx6441;
+use crate::aead::norx_mrs::NorxMRS;
+use crate::armor::Armored;
+use crate::kdf::balloon::{ self, Params };
+use crate::{ Error, Length, Decoding };
+
+
+const MAGIC: &[u8; 8] = b"SARKARA\x00";
+const VERSION: u8 = 1;
+const SALT_LENGTH: usize = 32;
+
+/// Upper bounds of the parameters `save` and `load` accept.
+pub const MAX_SPACE_COST: u32 = 1 << 24;
+pub const MAX_TIME_COST: u32 = 1 << 8;
+
+/// AEAD usable by the key file format.
+pub trait Cipher: AeadCipher {
+    const ID: u8;
+}
+
+impl Cipher for Norx6441 {
+    const ID: u8 = 1;
+}
+
+impl Cipher for NorxMRS {
+    const ID: u8 = 2;
+}
+
+/// Encrypt a key with a passphrase.
+///
+/// ```text
+/// magic || version || cipher id || space cost || time cost ||
+///     label length || label || salt || nonce || ciphertext
+/// ```
+///
+/// Everything before the ciphertext is authenticated as associated data.
+pub fn save<AE, T, R>(mut r: R, params: &Params, passphrase: &[u8], key: &T) -> Result<Vec<u8>, Error>
+    where
+        AE: Cipher,
+        T: Armored,
+        R: Rng + CryptoRng
+{
+    let max = Params { space_cost: MAX_SPACE_COST, time_cost: MAX_TIME_COST };
+    check_params(params, &max)?;
+
+    let mut salt = [0; SALT_LENGTH];
+    let mut nonce = vec![0; AE::NONCE_LENGTH];
+    r.fill_bytes(&mut salt);
+    r.fill_bytes(&mut nonce);
+
+    let mut output = Vec::new();
+    output.extend_from_slice(MAGIC);
+    output.push(VERSION);
+    output.push(AE::ID);
+    output.extend_from_slice(&params.space_cost.to_le_bytes());
+    output.extend_from_slice(&params.time_cost.to_le_bytes());
+    output.push(T::LABEL.len() as u8);
+    output.extend_from_slice(T::LABEL.as_bytes());
+    output.extend_from_slice(&salt);
+    output.extend_from_slice(&nonce);
+
+    let header_len = output.len();
+    output.resize(header_len + T::BYTES_LENGTH + AE::TAG_LENGTH, 0);
+    let (header, ciphertext) = output.split_at_mut(header_len);
+
+    let ae: AE = derive(params, passphrase, &salt);
+    key.read_bytes(|key| ae.seal(&nonce, header, key, ciphertext))
+        .expect("keyfile: length is checked");
+
+    Ok(output)
+}
+
+/// Decrypt a key with a passphrase.
+///
+/// `max` bounds the parameters the caller is willing to run,
+/// files asking for more are rejected before hashing.
+///
+/// Returns `Error::WrongPassphrase` if the passphrase is wrong or the file was modified.
+pub fn load<T: Armored>(max: &Params, passphrase: &[u8], input: &[u8]) -> Result<T, Error> {
+    let (magic, rest) = split(input, MAGIC.len())?;
+    let (version, rest) = split(rest, 1)?;
+    let (id, rest) = split(rest, 1)?;
+    let (space_cost, rest) = split(rest, 4)?;
+    let (time_cost, rest) = split(rest, 4)?;
+    let (label_len, rest) = split(rest, 1)?;
+    let (label, rest) = split(rest, usize::from(label_len[0]))?;
+    let (salt, rest) = split(rest, SALT_LENGTH)?;
+
+    if magic != MAGIC {
+        return Err(Error::Decoding(Decoding::Malformed));
+    }
+
+    if version != [VERSION] {
+        return Err(Error::Decoding(Decoding::Version));
+    }
+
+    if label != T::LABEL.as_bytes() {
+        return Err(Error::Decoding(Decoding::Label));
+    }
+
+    let mut space = [0; 4];
+    let mut time = [0; 4];
+    space.copy_from_slice(space_cost);
+    time.copy_from_slice(time_cost);
+    let params = Params {
+        space_cost: u32::from_le_bytes(space),
+        time_cost: u32::from_le_bytes(time)
+    };
+
+    check_params(&params, max)?;
+
+    let pos = input.len() - rest.len();
+    match id[0] {
+        Norx6441::ID => open::<Norx6441, T>(&params, passphrase, salt, input, pos),
+        NorxMRS::ID => open::<NorxMRS, T>(&params, passphrase, salt, input, pos),
+        _ => Err(Error::Decoding(Decoding::Algorithm))
+    }
+}
+
+fn open<AE: Cipher, T: Armored>(params: &Params, passphrase: &[u8], salt: &[u8], input: &[u8], pos: usize)
+    -> Result<T, Error>
+{
+    if input.len() != pos + AE::NONCE_LENGTH + T::BYTES_LENGTH + AE::TAG_LENGTH {
+        return Err(Error::Length(Length::Encoding));
+    }
+
+    let (header, ciphertext) = input.split_at(pos + AE::NONCE_LENGTH);
+    let nonce = &header[pos..];
+
+    let mut key: Vec<u8> = vec![0; T::BYTES_LENGTH];
+    let mut key = TempKey::from(&mut key as &mut [u8]);
+
+    let ae: AE = derive(params, passphrase, salt);
+    ae.open(nonce, header, ciphertext, &mut key)
+        .map_err(|err| match err {
+            Error::VerificationFailed => Error::WrongPassphrase,
+            err => err
+        })?;
+
+    Ok(T::from_bytes(&key))
+}
+
+fn derive<AE: AeadCipher>(params: &Params, passphrase: &[u8], salt: &[u8]) -> AE {
+    let mut key: Vec<u8> = vec![0; AE::KEY_LENGTH];
+    let mut key = TempKey::from(&mut key as &mut [u8]);
+
+    balloon::balloon(params, passphrase, salt, &mut key);
+    AE::new(&key)
+}
+
+fn check_params(params: &Params, max: &Params) -> Result<(), Error> {
+    if params.space_cost == 0 || params.space_cost > max.space_cost || params.space_cost > MAX_SPACE_COST
+        || params.time_cost == 0 || params.time_cost > max.time_cost || params.time_cost > MAX_TIME_COST
+    {
+        Err(Error::Decoding(Decoding::Parameters))
+    } else {
+        Ok(())
+    }
+}
+
+fn split(input: &[u8], n: usize) -> Result<(&[u8], &[u8]), Error> {
+    if input.len() >= n {
+        Ok(input.split_at(n))
+    } else {
+        Err(Error::Length(Length::Encoding))
+    }
+}
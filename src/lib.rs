@@ -1,6 +1,14 @@
 //! Sarkara is a Post-Quantum cryptography library.
+//!
+//! The core (`aead`, `kex`, `sign`, `hash`, `kdf`, `mac`) is `no_std`,
+//! the `alloc` feature enables Dilithium, `sealedbox`, encodings, key files
+//! and the protocols (`handshake`, `hpke`, `prekey`, `ratchet`),
+//! the `std` feature enables `io` adapters and OS entropy.
 
-#![feature(non_exhaustive)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
 #[cfg(feature = "serde")]
 extern crate serde;
@@ -8,10 +16,27 @@ extern crate serde;
 #[macro_use] mod common;
 pub mod sign;
 pub mod kex;
+pub mod hash;
 pub mod aead;
-pub mod sealedbox;
+pub mod kdf;
+pub mod mac;
+pub mod rand;
+#[cfg(feature = "alloc")] pub mod sealedbox;
+#[cfg(feature = "alloc")] pub mod armor;
+#[cfg(feature = "alloc")] pub mod pkcs8;
+#[cfg(feature = "alloc")] pub mod keyfile;
+#[cfg(feature = "alloc")] pub mod fingerprint;
+#[cfg(feature = "alloc")] pub mod registry;
+#[cfg(feature = "alloc")] pub mod handshake;
+#[cfg(feature = "alloc")] pub mod hpke;
+#[cfg(feature = "alloc")] pub mod prekey;
+#[cfg(feature = "alloc")] pub mod ratchet;
+#[cfg(feature = "ffi")] pub mod ffi;
+
+use core::fmt;
 
-use failure::Fail;
+
+pub const SEED_LENGTH: usize = 32;
 
 
 pub trait Packing: Sized {
@@ -26,13 +51,105 @@ pub trait Packing: Sized {
 }
 
 
-#[derive(Debug, Fail)]
+/// Errors never carry secret-dependent detail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[non_exhaustive]
 #[must_use]
 pub enum Error {
-    #[fail(display = "Input/Output length does not match")]
-    Length,
+    /// A length does not match.
+    Length(Length),
+
+    /// Input could not be decoded.
+    Decoding(Decoding),
 
-    #[fail(display = "Fail to pass verification")]
+    /// AEAD tag, MAC or KEM ciphertext failed to verify.
     VerificationFailed,
+
+    /// Signature failed to verify.
+    InvalidSignature,
+
+    /// Private key and public key do not belong together.
+    KeyMismatch,
+
+    /// Wrong passphrase or corrupted key file.
+    WrongPassphrase,
+
+    /// Operation out of order, or the session failed before.
+    InvalidState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Length {
+    Key,
+    Nonce,
+    Tag,
+    Output,
+    Encoding,
+    Context,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Decoding {
+    /// Structure is invalid.
+    Malformed,
+
+    /// Label does not match the expected type.
+    Label,
+
+    /// Checksum does not match.
+    Checksum,
+
+    /// Unsupported format version.
+    Version,
+
+    /// Algorithm identifier does not match or is unknown.
+    Algorithm,
+
+    /// Parameters are out of range.
+    Parameters,
 }
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Length(length) => write!(f, "Invalid {} length", length),
+            Error::Decoding(reason) => write!(f, "Fail to decode: {}", reason),
+            Error::VerificationFailed => f.write_str("Fail to pass verification"),
+            Error::InvalidSignature => f.write_str("Invalid signature"),
+            Error::KeyMismatch => f.write_str("Private key does not match public key"),
+            Error::WrongPassphrase => f.write_str("Wrong passphrase or corrupted key file"),
+            Error::InvalidState => f.write_str("Invalid protocol state")
+        }
+    }
+}
+
+impl fmt::Display for Length {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Length::Key => "key",
+            Length::Nonce => "nonce",
+            Length::Tag => "tag",
+            Length::Output => "output",
+            Length::Encoding => "encoding",
+            Length::Context => "context"
+        })
+    }
+}
+
+impl fmt::Display for Decoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Decoding::Malformed => "malformed input",
+            Decoding::Label => "label mismatch",
+            Decoding::Checksum => "checksum mismatch",
+            Decoding::Version => "unsupported version",
+            Decoding::Algorithm => "algorithm mismatch",
+            Decoding::Parameters => "invalid parameters"
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
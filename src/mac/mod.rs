@@ -0,0 +1,21 @@
+use crate::Error;
+
+pub mod norx;
+
+
+pub trait Mac: Sized {
+    const KEY_LENGTH: usize;
+    const TAG_LENGTH: usize;
+
+    /// Returns `Error::Length(Length::Key)` if the key is not `KEY_LENGTH` long.
+    ///
+    /// TODO should be `Self::KEY_LENGTH`
+    fn new(key: &[u8]) -> Result<Self, Error>;
+    fn update(&mut self, data: &[u8]);
+
+    /// TODO should be `tag: &mut [u8; Self::TAG_LENGTH]`
+    fn finalize(self, tag: &mut [u8]);
+
+    /// Compare the tag in constant time.
+    fn verify(self, tag: &[u8]) -> Result<(), Error>;
+}
@@ -0,0 +1,67 @@
+#[cfg(feature = "std")]
+use std::io;
+use arrayref::array_mut_ref;
+use crate::hash::{ Hash, Xof, XofReader };
+use crate::hash::norx::NorxHash;
+use crate::common::eq_ct;
+use crate::{ Error, Length };
+use super::Mac;
+
+
+pub const KEY_LENGTH: usize = 32;
+pub const TAG_LENGTH: usize = 32;
+
+const MAC: u8 = 0x04;
+
+pub struct NorxMac(NorxHash);
+
+impl Mac for NorxMac {
+    const KEY_LENGTH: usize = KEY_LENGTH;
+    const TAG_LENGTH: usize = TAG_LENGTH;
+
+    fn new(key: &[u8]) -> Result<Self, Error> {
+        if key.len() != KEY_LENGTH {
+            return Err(Error::Length(Length::Key));
+        }
+
+        let mut hasher = NorxHash::with_domain(MAC);
+        hasher.update_framed(key);
+        Ok(NorxMac(hasher))
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data)
+    }
+
+    fn finalize(self, tag: &mut [u8]) {
+        let tag = array_mut_ref!(tag, 0, TAG_LENGTH);
+        self.0.xof().read(tag);
+    }
+
+    fn verify(self, tag: &[u8]) -> Result<(), Error> {
+        if tag.len() != TAG_LENGTH {
+            return Err(Error::Length(Length::Tag));
+        }
+
+        let mut expected = [0; TAG_LENGTH];
+        self.finalize(&mut expected);
+
+        if eq_ct(&expected, tag) {
+            Ok(())
+        } else {
+            Err(Error::VerificationFailed)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl io::Write for NorxMac {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+//! Just enough DER for PKCS#8 and SubjectPublicKeyInfo.
+
+use alloc::vec::Vec;
+use crate::{ Error, Decoding };
+
+
+pub const INTEGER: u8 = 0x02;
+pub const BIT_STRING: u8 = 0x03;
+pub const OCTET_STRING: u8 = 0x04;
+pub const OBJECT_IDENTIFIER: u8 = 0x06;
+pub const SEQUENCE: u8 = 0x30;
+
+pub fn write(output: &mut Vec<u8>, tag: u8, content: &[u8]) {
+    output.push(tag);
+
+    let len = content.len();
+    if len < 0x80 {
+        output.push(len as u8);
+    } else {
+        let bytes = len.to_be_bytes();
+        let skip = bytes.iter().take_while(|&&b| b == 0).count();
+        output.push(0x80 | (bytes.len() - skip) as u8);
+        output.extend_from_slice(&bytes[skip..]);
+    }
+
+    output.extend_from_slice(content);
+}
+
+/// Read one TLV with the expected tag, returns the content and the rest.
+pub fn read(input: &[u8], tag: u8) -> Result<(&[u8], &[u8]), Error> {
+    let (&t, input) = input.split_first().ok_or(Error::Decoding(Decoding::Malformed))?;
+    let (&len, mut input) = input.split_first().ok_or(Error::Decoding(Decoding::Malformed))?;
+
+    if t != tag {
+        return Err(Error::Decoding(Decoding::Malformed));
+    }
+
+    let len = if len < 0x80 {
+        usize::from(len)
+    } else {
+        let n = usize::from(len & 0x7f);
+        if n == 0 || n > 4 || input.len() < n || input[0] == 0 {
+            return Err(Error::Decoding(Decoding::Malformed));
+        }
+
+        let (bytes, rest) = input.split_at(n);
+        input = rest;
+        let len = bytes.iter().fold(0, |sum, &b| (sum << 8) | usize::from(b));
+
+        // DER requires the shortest form
+        if len < 0x80 {
+            return Err(Error::Decoding(Decoding::Malformed));
+        }
+        len
+    };
+
+    if input.len() < len {
+        return Err(Error::Decoding(Decoding::Malformed));
+    }
+
+    Ok(input.split_at(len))
+}
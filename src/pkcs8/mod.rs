@@ -0,0 +1,115 @@
+use alloc::vec::Vec;
+use crate::{ Packing, Error, Length, Decoding };
+
+mod der;
+
+
+/// An algorithm with a X.509 algorithm identifier.
+///
+/// There are no standardized identifiers for these parameter sets and encodings,
+/// so the OIDs are under a private UUID arc (X.667),
+/// `2.25.316991036999128169988469511729626057660`.
+/// Other tools will not recognize them.
+pub trait AlgorithmIdentifier {
+    type PrivateKey: Packing;
+    type PublicKey: Packing;
+
+    /// DER encoded content of the OBJECT IDENTIFIER.
+    const OID: &'static [u8];
+}
+
+/// Encode private key as PKCS#8 `PrivateKeyInfo`.
+pub fn to_pkcs8<A: AlgorithmIdentifier>(sk: &A::PrivateKey) -> Vec<u8> {
+    let mut info = Vec::with_capacity(A::PrivateKey::BYTES_LENGTH + 32);
+    der::write(&mut info, der::INTEGER, &[0]);
+    algorithm::<A>(&mut info);
+    sk.read_bytes(|sk| der::write(&mut info, der::OCTET_STRING, sk));
+
+    let mut output = Vec::with_capacity(info.len() + 4);
+    der::write(&mut output, der::SEQUENCE, &info);
+    output
+}
+
+/// Decode private key from PKCS#8 `PrivateKeyInfo`, the OID must match `A`.
+pub fn from_pkcs8<A: AlgorithmIdentifier>(input: &[u8]) -> Result<A::PrivateKey, Error> {
+    let (info, rest) = der::read(input, der::SEQUENCE)?;
+    check_end(rest)?;
+
+    let (version, info) = der::read(info, der::INTEGER)?;
+    if version != [0] {
+        return Err(Error::Decoding(Decoding::Version));
+    }
+
+    let info = check_algorithm::<A>(info)?;
+    let (sk, rest) = der::read(info, der::OCTET_STRING)?;
+    check_end(rest)?;
+
+    if sk.len() != A::PrivateKey::BYTES_LENGTH {
+        return Err(Error::Length(Length::Key));
+    }
+
+    Ok(A::PrivateKey::from_bytes(sk))
+}
+
+/// Encode public key as `SubjectPublicKeyInfo`.
+pub fn to_spki<A: AlgorithmIdentifier>(pk: &A::PublicKey) -> Vec<u8> {
+    let mut info = Vec::with_capacity(A::PublicKey::BYTES_LENGTH + 32);
+    algorithm::<A>(&mut info);
+    pk.read_bytes(|pk| {
+        let mut bits = Vec::with_capacity(pk.len() + 1);
+        bits.push(0);
+        bits.extend_from_slice(pk);
+        der::write(&mut info, der::BIT_STRING, &bits);
+    });
+
+    let mut output = Vec::with_capacity(info.len() + 4);
+    der::write(&mut output, der::SEQUENCE, &info);
+    output
+}
+
+/// Decode public key from `SubjectPublicKeyInfo`, the OID must match `A`.
+pub fn from_spki<A: AlgorithmIdentifier>(input: &[u8]) -> Result<A::PublicKey, Error> {
+    let (info, rest) = der::read(input, der::SEQUENCE)?;
+    check_end(rest)?;
+
+    let info = check_algorithm::<A>(info)?;
+    let (bits, rest) = der::read(info, der::BIT_STRING)?;
+    check_end(rest)?;
+
+    let pk = match bits.split_first() {
+        Some((0, pk)) => pk,
+        _ => return Err(Error::Decoding(Decoding::Malformed))
+    };
+
+    if pk.len() != A::PublicKey::BYTES_LENGTH {
+        return Err(Error::Length(Length::Key));
+    }
+
+    Ok(A::PublicKey::from_bytes(pk))
+}
+
+fn algorithm<A: AlgorithmIdentifier>(output: &mut Vec<u8>) {
+    let mut oid = Vec::with_capacity(A::OID.len() + 2);
+    der::write(&mut oid, der::OBJECT_IDENTIFIER, A::OID);
+    der::write(output, der::SEQUENCE, &oid);
+}
+
+fn check_algorithm<A: AlgorithmIdentifier>(input: &[u8]) -> Result<&[u8], Error> {
+    let (algorithm, rest) = der::read(input, der::SEQUENCE)?;
+    let (oid, params) = der::read(algorithm, der::OBJECT_IDENTIFIER)?;
+    check_end(params)?;
+
+    if oid == A::OID {
+        Ok(rest)
+    } else {
+        Err(Error::Decoding(Decoding::Algorithm))
+    }
+}
+
+fn check_end(rest: &[u8]) -> Result<(), Error> {
+    if rest.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Decoding(Decoding::Malformed))
+    }
+}
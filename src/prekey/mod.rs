@@ -0,0 +1,461 @@
+//! Prekey bundles for asynchronous key agreement, in the style of X3DH.
+//!
+//! The responder publishes a Kyber prekey signed by its identity key and
+//! one-time prekeys. The initiator verifies the bundle, encapsulates to the
+//! prekeys and signs the initial message with its own identity key.
+//!
+//! The initiator must check that `Bundle::identity` is the expected peer,
+//! e.g. by comparing fingerprints.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use alloc::collections::BTreeMap;
+use rand::{ Rng, CryptoRng };
+use seckey::TempKey;
+use crate::kex::{ KeyExchange, CheckedExchange };
+use crate::sign::Signature;
+use crate::kdf::{ Kdf, norx::NorxKdf };
+use crate::common::zero;
+use crate::{ Packing, Error, Length, Decoding };
+
+
+pub const ROOT_KEY_LENGTH: usize = 32;
+
+const SALT: &[u8] = b"sarkara prekey";
+const SIGNED_PREKEY: &[u8] = b"sarkara signed prekey";
+const INITIAL_MESSAGE: &[u8] = b"sarkara prekey message";
+
+pub struct SignedPrekey<KEX: KeyExchange, SS: Signature> {
+    pub id: u32,
+    pub key: KEX::PublicKey,
+    pub signature: SS::Signature
+}
+
+pub struct OneTimePrekey<KEX: KeyExchange> {
+    pub id: u32,
+    pub key: KEX::PublicKey
+}
+
+pub struct Bundle<KEX: KeyExchange, SS: Signature> {
+    pub identity: SS::PublicKey,
+    pub signed_prekey: SignedPrekey<KEX, SS>,
+    pub one_time_prekey: Option<OneTimePrekey<KEX>>
+}
+
+/// Sent by the initiator along with its first message.
+pub struct InitialMessage<KEX: KeyExchange, SS: Signature> {
+    pub identity: SS::PublicKey,
+    pub signed_prekey_id: u32,
+    pub signed_prekey_message: KEX::Message,
+    pub one_time_prekey: Option<(u32, KEX::Message)>,
+    pub signature: SS::Signature
+}
+
+/// The responder prekeys, private halves included.
+///
+/// The previous signed prekey is kept after a rotation for messages in flight.
+pub struct Prekeys<KEX: KeyExchange, SS: Signature> {
+    identity: (SS::PrivateKey, SS::PublicKey),
+    signed_prekey: (u32, KEX::PrivateKey, KEX::PublicKey, SS::Signature),
+    previous: Option<(u32, KEX::PrivateKey)>,
+    one_time_prekeys: BTreeMap<u32, KEX::PrivateKey>,
+    next_id: u32
+}
+
+pub struct RootKey([u8; ROOT_KEY_LENGTH]);
+
+
+impl<KEX, SS> Prekeys<KEX, SS>
+    where
+        KEX: CheckedExchange,
+        SS: Signature
+{
+    pub fn new<R: Rng + CryptoRng>(mut r: R, identity: (SS::PrivateKey, SS::PublicKey)) -> Self {
+        let signed_prekey = generate_signed::<KEX, SS, _>(&mut r, &identity.0, 0);
+
+        Prekeys {
+            identity, signed_prekey,
+            previous: None,
+            one_time_prekeys: BTreeMap::new(),
+            next_id: 1
+        }
+    }
+
+    pub fn identity(&self) -> &SS::PublicKey {
+        &self.identity.1
+    }
+
+    pub fn signed_prekey(&self) -> SignedPrekey<KEX, SS> {
+        let (id, _, ref pk, ref sig) = self.signed_prekey;
+        SignedPrekey { id, key: copy(pk), signature: copy(sig) }
+    }
+
+    /// Replace the signed prekey, the previous one stays usable until the next rotation.
+    ///
+    /// Returns `InvalidState` once the prekey ids are exhausted.
+    pub fn rotate_signed_prekey<R: Rng + CryptoRng>(&mut self, r: R) -> Result<(), Error> {
+        let id = self.next_id()?;
+        let signed_prekey = generate_signed::<KEX, SS, _>(r, &self.identity.0, id);
+        let (id, sk, ..) = core::mem::replace(&mut self.signed_prekey, signed_prekey);
+        self.previous = Some((id, sk));
+        Ok(())
+    }
+
+    /// Publish the returned keys, each one is usable once.
+    ///
+    /// Returns `InvalidState` once the prekey ids are exhausted.
+    pub fn generate_one_time_prekeys<R: Rng + CryptoRng>(&mut self, mut r: R, n: usize)
+        -> Result<Vec<OneTimePrekey<KEX>>, Error>
+    {
+        (0..n)
+            .map(|_| {
+                let id = self.next_id()?;
+                let (sk, pk) = KEX::keypair(&mut r);
+                self.one_time_prekeys.insert(id, sk);
+                Ok(OneTimePrekey { id, key: pk })
+            })
+            .collect()
+    }
+
+    /// Number of unused one-time prekeys.
+    pub fn one_time_prekeys(&self) -> usize {
+        self.one_time_prekeys.len()
+    }
+
+    /// A bundle with a fresh one-time prekey.
+    pub fn bundle<R: Rng + CryptoRng>(&mut self, r: R) -> Result<Bundle<KEX, SS>, Error> {
+        Ok(Bundle {
+            identity: copy(&self.identity.1),
+            signed_prekey: self.signed_prekey(),
+            one_time_prekey: self.generate_one_time_prekeys(r, 1)?.pop()
+        })
+    }
+
+    /// Derive the root key, the one-time prekey is consumed on success.
+    ///
+    /// Returns `InvalidState` if a prekey is unknown or already used.
+    /// Without a one-time prekey the message can be replayed, as in X3DH.
+    pub fn respond(&mut self, message: &InitialMessage<KEX, SS>) -> Result<RootKey, Error> {
+        let (spk_sk, spk_pk) = match (&self.signed_prekey, &self.previous) {
+            (&(id, ref sk, ref pk, _), _) if id == message.signed_prekey_id => (sk, copy(pk)),
+            (_, &Some((id, ref sk))) if id == message.signed_prekey_id => (sk, KEX::public_key(sk)),
+            _ => return Err(Error::InvalidState)
+        };
+
+        let mut sharedkey: Vec<u8> = vec![0; 2 * KEX::SHARED_LENGTH];
+        let mut sharedkey = TempKey::from(&mut sharedkey as &mut [u8]);
+        let (ss1, ss2) = sharedkey.split_at_mut(KEX::SHARED_LENGTH);
+
+        <KEX as CheckedExchange>::exchange_from(ss1, spk_sk, &message.signed_prekey_message)?;
+
+        let otp = match message.one_time_prekey {
+            Some((id, ref m)) => {
+                let sk = self.one_time_prekeys.get(&id).ok_or(Error::InvalidState)?;
+                <KEX as CheckedExchange>::exchange_from(ss2, sk, m)?;
+                Some((id, KEX::public_key(sk), m))
+            },
+            None => None
+        };
+
+        let transcript = transcript::<KEX, SS>(
+            &self.identity.1, &message.identity,
+            message.signed_prekey_id, &spk_pk, &message.signed_prekey_message,
+            otp.as_ref().map(|&(id, ref pk, m)| (id, pk, m))
+        );
+        SS::verify(&message.identity, &message.signature, &transcript)?;
+
+        if let Some((id, ..)) = otp {
+            self.one_time_prekeys.remove(&id);
+        }
+
+        let len = if message.one_time_prekey.is_some() { 2 } else { 1 } * KEX::SHARED_LENGTH;
+        Ok(RootKey::derive(&sharedkey[..len], &transcript))
+    }
+
+    /// Contains the private keys, store it encrypted.
+    ///
+    /// ```text
+    /// identity sk || identity pk || id || prekey sk || prekey pk || signature ||
+    ///     flag [|| id || prekey sk] || next id || count || (id || one-time prekey sk)*
+    /// ```
+    ///
+    /// Ids and count in big endian.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let (ref isk, ref ipk) = self.identity;
+        let (id, ref sk, ref pk, ref sig) = self.signed_prekey;
+
+        let mut output = Vec::new();
+        isk.read_bytes(|x| output.extend_from_slice(x));
+        ipk.read_bytes(|x| output.extend_from_slice(x));
+        output.extend_from_slice(&id.to_be_bytes());
+        sk.read_bytes(|x| output.extend_from_slice(x));
+        pk.read_bytes(|x| output.extend_from_slice(x));
+        sig.read_bytes(|x| output.extend_from_slice(x));
+        write_option(&mut output, self.previous.as_ref().map(|(id, sk)| (*id, sk)));
+        output.extend_from_slice(&self.next_id.to_be_bytes());
+        output.extend_from_slice(&(self.one_time_prekeys.len() as u32).to_be_bytes());
+        for (id, sk) in &self.one_time_prekeys {
+            output.extend_from_slice(&id.to_be_bytes());
+            sk.read_bytes(|x| output.extend_from_slice(x));
+        }
+        output
+    }
+
+    /// Checks that the keys belong together and the ids were issued.
+    pub fn from_bytes(mut input: &[u8]) -> Result<Self, Error> {
+        let isk = read::<SS::PrivateKey>(&mut input)?;
+        let ipk = read::<SS::PublicKey>(&mut input)?;
+        let id = read_id(&mut input)?;
+        let sk = read::<KEX::PrivateKey>(&mut input)?;
+        let pk = read::<KEX::PublicKey>(&mut input)?;
+        let sig = read::<SS::Signature>(&mut input)?;
+        let previous = read_option(&mut input)?;
+        let next_id = read_id(&mut input)?;
+        let count = read_id(&mut input)? as usize;
+
+        if input.len() / (4 + KEX::PrivateKey::BYTES_LENGTH) < count {
+            return Err(Error::Length(Length::Encoding));
+        }
+        let mut one_time_prekeys = BTreeMap::new();
+        for _ in 0..count {
+            let otp_id = read_id(&mut input)?;
+            if otp_id >= next_id || one_time_prekeys.insert(otp_id, read(&mut input)?).is_some() {
+                return Err(Error::Decoding(Decoding::Malformed));
+            }
+        }
+        finish(input)?;
+
+        SS::check_keypair(&isk, &ipk)?;
+        KEX::check_keypair(&sk, &pk)?;
+        SS::verify(&ipk, &sig, &signed_prekey_data::<KEX>(id, &pk))?;
+        if id >= next_id || previous.as_ref().map(|&(pid, _)| pid >= next_id).unwrap_or(false) {
+            return Err(Error::Decoding(Decoding::Malformed));
+        }
+
+        Ok(Prekeys {
+            identity: (isk, ipk),
+            signed_prekey: (id, sk, pk, sig),
+            previous, one_time_prekeys, next_id
+        })
+    }
+
+    fn next_id(&mut self) -> Result<u32, Error> {
+        let id = self.next_id;
+        self.next_id = self.next_id.checked_add(1).ok_or(Error::InvalidState)?;
+        Ok(id)
+    }
+}
+
+/// Verify the bundle, encapsulate to its prekeys and derive the root key.
+pub fn initiate<KEX, SS, R>(
+    mut r: R,
+    identity: &(SS::PrivateKey, SS::PublicKey),
+    bundle: &Bundle<KEX, SS>
+) -> Result<(InitialMessage<KEX, SS>, RootKey), Error>
+    where
+        KEX: KeyExchange,
+        SS: Signature,
+        R: Rng + CryptoRng
+{
+    let spk = &bundle.signed_prekey;
+    SS::verify(&bundle.identity, &spk.signature, &signed_prekey_data::<KEX>(spk.id, &spk.key))?;
+
+    let mut sharedkey: Vec<u8> = vec![0; 2 * KEX::SHARED_LENGTH];
+    let mut sharedkey = TempKey::from(&mut sharedkey as &mut [u8]);
+    let (ss1, ss2) = sharedkey.split_at_mut(KEX::SHARED_LENGTH);
+
+    let m1 = KEX::exchange_to(&mut r, ss1, &spk.key);
+    let m2 = bundle.one_time_prekey.as_ref()
+        .map(|otp| (otp, KEX::exchange_to(&mut r, ss2, &otp.key)));
+    let len = if m2.is_some() { 2 } else { 1 } * KEX::SHARED_LENGTH;
+
+    let transcript = transcript::<KEX, SS>(
+        &bundle.identity, &identity.1,
+        spk.id, &spk.key, &m1,
+        m2.as_ref().map(|(otp, m)| (otp.id, &otp.key, m))
+    );
+    let signature = SS::signature(&mut r, &identity.0, &transcript);
+    let root = RootKey::derive(&sharedkey[..len], &transcript);
+
+    let message = InitialMessage {
+        identity: copy(&identity.1),
+        signed_prekey_id: spk.id,
+        signed_prekey_message: m1,
+        one_time_prekey: m2.map(|(otp, m)| (otp.id, m)),
+        signature
+    };
+
+    Ok((message, root))
+}
+
+
+impl RootKey {
+    fn derive(sharedkey: &[u8], transcript: &[u8]) -> RootKey {
+        let mut root = [0; ROOT_KEY_LENGTH];
+        NorxKdf::extract(SALT, sharedkey).expand(transcript, &mut root);
+        RootKey(root)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; ROOT_KEY_LENGTH] {
+        &self.0
+    }
+}
+
+impl Drop for RootKey {
+    fn drop(&mut self) {
+        zero(&mut self.0);
+    }
+}
+
+impl<KEX: KeyExchange, SS: Signature> Bundle<KEX, SS> {
+    /// `identity || id || prekey || signature || flag [|| id || prekey]`, ids in big endian.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut output = Vec::new();
+        self.identity.read_bytes(|x| output.extend_from_slice(x));
+        output.extend_from_slice(&self.signed_prekey.id.to_be_bytes());
+        self.signed_prekey.key.read_bytes(|x| output.extend_from_slice(x));
+        self.signed_prekey.signature.read_bytes(|x| output.extend_from_slice(x));
+        write_option(&mut output, self.one_time_prekey.as_ref().map(|otp| (otp.id, &otp.key)));
+        output
+    }
+
+    pub fn from_bytes(mut input: &[u8]) -> Result<Self, Error> {
+        let identity = read(&mut input)?;
+        let id = read_id(&mut input)?;
+        let key = read(&mut input)?;
+        let signature = read(&mut input)?;
+        let one_time_prekey = read_option(&mut input)?
+            .map(|(id, key)| OneTimePrekey { id, key });
+        finish(input)?;
+
+        Ok(Bundle {
+            identity,
+            signed_prekey: SignedPrekey { id, key, signature },
+            one_time_prekey
+        })
+    }
+}
+
+impl<KEX: KeyExchange, SS: Signature> InitialMessage<KEX, SS> {
+    /// `identity || id || message || signature || flag [|| id || message]`, ids in big endian.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut output = Vec::new();
+        self.identity.read_bytes(|x| output.extend_from_slice(x));
+        output.extend_from_slice(&self.signed_prekey_id.to_be_bytes());
+        self.signed_prekey_message.read_bytes(|x| output.extend_from_slice(x));
+        self.signature.read_bytes(|x| output.extend_from_slice(x));
+        write_option(&mut output, self.one_time_prekey.as_ref().map(|(id, m)| (*id, m)));
+        output
+    }
+
+    pub fn from_bytes(mut input: &[u8]) -> Result<Self, Error> {
+        let identity = read(&mut input)?;
+        let signed_prekey_id = read_id(&mut input)?;
+        let signed_prekey_message = read(&mut input)?;
+        let signature = read(&mut input)?;
+        let one_time_prekey = read_option(&mut input)?;
+        finish(input)?;
+
+        Ok(InitialMessage { identity, signed_prekey_id, signed_prekey_message, one_time_prekey, signature })
+    }
+}
+
+
+fn generate_signed<KEX, SS, R>(mut r: R, identity: &SS::PrivateKey, id: u32)
+    -> (u32, KEX::PrivateKey, KEX::PublicKey, SS::Signature)
+    where
+        KEX: KeyExchange,
+        SS: Signature,
+        R: Rng + CryptoRng
+{
+    let (sk, pk) = KEX::keypair(&mut r);
+    let sig = SS::signature(&mut r, identity, &signed_prekey_data::<KEX>(id, &pk));
+    (id, sk, pk, sig)
+}
+
+fn signed_prekey_data<KEX: KeyExchange>(id: u32, pk: &KEX::PublicKey) -> Vec<u8> {
+    let mut data = Vec::with_capacity(SIGNED_PREKEY.len() + 4 + KEX::PublicKey::BYTES_LENGTH);
+    data.extend_from_slice(SIGNED_PREKEY);
+    data.extend_from_slice(&id.to_be_bytes());
+    pk.read_bytes(|pk| data.extend_from_slice(pk));
+    data
+}
+
+/// All fields are fixed length, except the trailing optional one-time prekey.
+fn transcript<KEX: KeyExchange, SS: Signature>(
+    responder: &SS::PublicKey,
+    initiator: &SS::PublicKey,
+    spk_id: u32,
+    spk: &KEX::PublicKey,
+    m1: &KEX::Message,
+    otp: Option<(u32, &KEX::PublicKey, &KEX::Message)>
+) -> Vec<u8> {
+    let mut data = Vec::new();
+    data.extend_from_slice(INITIAL_MESSAGE);
+    responder.read_bytes(|x| data.extend_from_slice(x));
+    initiator.read_bytes(|x| data.extend_from_slice(x));
+    data.extend_from_slice(&spk_id.to_be_bytes());
+    spk.read_bytes(|x| data.extend_from_slice(x));
+    m1.read_bytes(|x| data.extend_from_slice(x));
+
+    if let Some((id, pk, m)) = otp {
+        data.push(1);
+        data.extend_from_slice(&id.to_be_bytes());
+        pk.read_bytes(|x| data.extend_from_slice(x));
+        m.read_bytes(|x| data.extend_from_slice(x));
+    } else {
+        data.push(0);
+    }
+
+    data
+}
+
+fn copy<T: Packing>(t: &T) -> T {
+    t.read_bytes(T::from_bytes)
+}
+
+fn write_option<T: Packing>(output: &mut Vec<u8>, value: Option<(u32, &T)>) {
+    if let Some((id, t)) = value {
+        output.push(1);
+        output.extend_from_slice(&id.to_be_bytes());
+        t.read_bytes(|x| output.extend_from_slice(x));
+    } else {
+        output.push(0);
+    }
+}
+
+fn take<'a>(input: &mut &'a [u8], len: usize) -> Result<&'a [u8], Error> {
+    if input.len() < len {
+        return Err(Error::Length(Length::Encoding));
+    }
+
+    let (head, tail) = input.split_at(len);
+    *input = tail;
+    Ok(head)
+}
+
+fn read<T: Packing>(input: &mut &[u8]) -> Result<T, Error> {
+    take(input, T::BYTES_LENGTH).map(T::from_bytes)
+}
+
+fn read_id(input: &mut &[u8]) -> Result<u32, Error> {
+    let mut id = [0; 4];
+    id.copy_from_slice(take(input, 4)?);
+    Ok(u32::from_be_bytes(id))
+}
+
+fn read_option<T: Packing>(input: &mut &[u8]) -> Result<Option<(u32, T)>, Error> {
+    match take(input, 1)?[0] {
+        0 => Ok(None),
+        1 => Ok(Some((read_id(input)?, read(input)?))),
+        _ => Err(Error::Decoding(Decoding::Malformed))
+    }
+}
+
+fn finish(input: &[u8]) -> Result<(), Error> {
+    if input.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Length(Length::Encoding))
+    }
+}
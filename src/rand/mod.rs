@@ -0,0 +1,5 @@
+pub mod norx;
+
+
+/// Number of requests served before an entropy-backed generator reseeds itself.
+pub const RESEED_INTERVAL: u64 = 1 << 16;
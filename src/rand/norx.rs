@@ -0,0 +1,143 @@
+#[cfg(feature = "std")]
+use std::process;
+#[cfg(feature = "std")]
+use rand::OsRng;
+use rand::{ RngCore, CryptoRng, SeedableRng, Error };
+use crate::hash::{ Hash, Xof, XofReader };
+use crate::hash::norx::NorxHash;
+use crate::common::zero;
+use crate::SEED_LENGTH;
+#[cfg(feature = "std")]
+use super::RESEED_INTERVAL;
+
+
+const RESEED: u8 = 0x05;
+const GENERATE: u8 = 0x06;
+
+/// A DRBG with fast key erasure over the NORX permutation.
+///
+/// Generators created with `new` draw entropy from the OS and reseed every
+/// `RESEED_INTERVAL` requests; generators created with `from_seed` are
+/// deterministic and only reseed when asked.
+/// Without `std` there is no OS entropy and no fork detection.
+pub struct NorxRng {
+    key: [u8; SEED_LENGTH],
+    counter: u64,
+    #[cfg(feature = "std")] pid: u32,
+    #[cfg(feature = "std")] os: Option<OsRng>
+}
+
+impl NorxRng {
+    #[cfg(feature = "std")]
+    pub fn new() -> Result<NorxRng, Error> {
+        let mut os = OsRng::new()?;
+        let mut seed = [0; SEED_LENGTH];
+        os.try_fill_bytes(&mut seed)?;
+
+        let mut rng = NorxRng::from_seed(seed);
+        rng.os = Some(os);
+        Ok(rng)
+    }
+
+    /// Mix additional entropy into the generator and reset the reseed counter.
+    pub fn reseed(&mut self, entropy: &[u8]) {
+        let mut hasher = NorxHash::with_domain(RESEED);
+        hasher.update_framed(&self.key);
+        hasher.update_framed(entropy);
+        hasher.xof().read(&mut self.key);
+        self.counter = 0;
+    }
+
+    /// Number of requests served since the last reseed.
+    pub fn reseed_counter(&self) -> u64 {
+        self.counter
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn check(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[cfg(feature = "std")]
+    fn check(&mut self) -> Result<(), Error> {
+        let pid = process::id();
+
+        if pid != self.pid {
+            // forked, the child must not repeat the parent stream.
+            self.pid = pid;
+            self.reseed(&pid.to_le_bytes());
+
+            if let Some(os) = self.os.as_mut() {
+                let mut entropy = [0; SEED_LENGTH];
+                os.try_fill_bytes(&mut entropy)?;
+                self.reseed(&entropy);
+            }
+        }
+
+        if self.counter >= RESEED_INTERVAL {
+            if let Some(os) = self.os.as_mut() {
+                let mut entropy = [0; SEED_LENGTH];
+                os.try_fill_bytes(&mut entropy)?;
+                self.reseed(&entropy);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl SeedableRng for NorxRng {
+    type Seed = [u8; SEED_LENGTH];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let mut rng = NorxRng {
+            key: [0; SEED_LENGTH],
+            counter: 0,
+            #[cfg(feature = "std")] pid: process::id(),
+            #[cfg(feature = "std")] os: None
+        };
+        rng.reseed(&seed);
+        rng
+    }
+}
+
+impl RngCore for NorxRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut buf = [0; 4];
+        self.fill_bytes(&mut buf);
+        u32::from_le_bytes(buf)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut buf = [0; 8];
+        self.fill_bytes(&mut buf);
+        u64::from_le_bytes(buf)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.try_fill_bytes(dest)
+            .expect("NorxRng: failed to reseed from OS")
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.check()?;
+
+        let mut hasher = NorxHash::with_domain(GENERATE);
+        hasher.update_framed(&self.key);
+        hasher.update(&self.counter.to_le_bytes());
+        let mut reader = hasher.xof();
+        reader.read(&mut self.key);
+        reader.read(dest);
+
+        self.counter += 1;
+        Ok(())
+    }
+}
+
+impl CryptoRng for NorxRng {}
+
+impl Drop for NorxRng {
+    fn drop(&mut self) {
+        zero(&mut self.key);
+    }
+}
@@ -0,0 +1,317 @@
+//! Double ratchet with a KEM based asymmetric step.
+//!
+//! Every sending epoch starts with a fresh keypair and an encapsulation to the
+//! latest remote public key, both are carried in every message header of the epoch.
+//! Within an epoch a symmetric chain derives a key per message.
+//!
+//! Keys of skipped messages are kept for out of order delivery,
+//! at most `MAX_SKIP` per chain and `MAX_SKIPPED_KEYS` in total, oldest evicted first.
+
+use core::marker::PhantomData;
+use alloc::vec;
+use alloc::vec::Vec;
+use alloc::collections::{ BTreeMap, VecDeque };
+use rand::{ Rng, CryptoRng };
+use crate::aead::AeadCipher;
+use crate::kex::{ KeyExchange, CheckedExchange };
+use crate::hash::{ Hash, norx::NorxHash };
+use crate::kdf::{ Kdf, norx::NorxKdf };
+use crate::common::zero;
+use crate::{ Packing, Error, Length };
+
+
+pub const ROOT_KEY_LENGTH: usize = 32;
+pub const MAX_SKIP: u32 = 1000;
+pub const MAX_SKIPPED_KEYS: usize = 2000;
+
+const KEY_LENGTH: usize = 32;
+
+type Epoch = [u8; 32];
+
+pub struct Ratchet<KEX: KeyExchange, AE: AeadCipher> {
+    root: [u8; ROOT_KEY_LENGTH],
+    keypair: Option<(KEX::PrivateKey, KEX::PublicKey)>,
+    remote: Option<(KEX::PublicKey, Epoch)>,
+    send: Option<(Chain, KEX::Message)>,
+    recv: Option<Chain>,
+    previous: u32,
+    skipped: BTreeMap<(Epoch, u32), Vec<u8>>,
+    order: VecDeque<(Epoch, u32)>,
+    _phantom: PhantomData<AE>
+}
+
+#[derive(Clone)]
+struct Chain {
+    key: [u8; KEY_LENGTH],
+    n: u32
+}
+
+struct Header<KEX: KeyExchange> {
+    pk: KEX::PublicKey,
+    m: KEX::Message,
+    previous: u32,
+    n: u32
+}
+
+
+impl<KEX, AE> Ratchet<KEX, AE>
+    where
+        KEX: CheckedExchange,
+        AE: AeadCipher
+{
+    /// `root` is a shared secret, e.g. `prekey::RootKey` or an `hpke::Context` export.
+    /// `remote` is the responder ratchet key, the initiator sends first.
+    pub fn initiator(root: &[u8], remote: KEX::PublicKey) -> Result<Self, Error> {
+        let mut ratchet = Self::new(root, None)?;
+        let epoch = epoch::<KEX>(&remote);
+        ratchet.remote = Some((remote, epoch));
+        Ok(ratchet)
+    }
+
+    pub fn responder(root: &[u8], keypair: (KEX::PrivateKey, KEX::PublicKey)) -> Result<Self, Error> {
+        Self::new(root, Some(keypair))
+    }
+
+    fn new(root: &[u8], keypair: Option<(KEX::PrivateKey, KEX::PublicKey)>) -> Result<Self, Error> {
+        if root.len() != ROOT_KEY_LENGTH {
+            return Err(Error::Length(Length::Key));
+        }
+
+        let mut r = [0; ROOT_KEY_LENGTH];
+        r.copy_from_slice(root);
+
+        Ok(Ratchet {
+            root: r, keypair,
+            remote: None, send: None, recv: None,
+            previous: 0,
+            skipped: BTreeMap::new(),
+            order: VecDeque::new(),
+            _phantom: PhantomData
+        })
+    }
+
+    /// Returns `header || ciphertext`, `ad` is authenticated but not sent.
+    pub fn encrypt<R: Rng + CryptoRng>(&mut self, mut r: R, ad: &[u8], input: &[u8]) -> Result<Vec<u8>, Error> {
+        if self.send.is_none() {
+            let (remote, _) = self.remote.as_ref().ok_or(Error::InvalidState)?;
+
+            let mut sharedkey: Vec<u8> = vec![0; KEX::SHARED_LENGTH];
+            let m = KEX::exchange_to(&mut r, &mut sharedkey, remote);
+            let (root, key) = kdf_rk(&self.root, &sharedkey);
+            zero(&mut sharedkey);
+
+            self.root = root;
+            self.keypair = Some(KEX::keypair(&mut r));
+            self.send = Some((Chain { key, n: 0 }, m));
+        }
+
+        let previous = self.previous;
+        let (_, pk) = self.keypair.as_ref().ok_or(Error::InvalidState)?;
+        let (chain, m) = self.send.as_mut().ok_or(Error::InvalidState)?;
+        if chain.n == u32::MAX {
+            return Err(Error::InvalidState);
+        }
+
+        let mut output = Vec::with_capacity(header_length::<KEX>() + input.len() + AE::TAG_LENGTH);
+        pk.read_bytes(|x| output.extend_from_slice(x));
+        m.read_bytes(|x| output.extend_from_slice(x));
+        output.extend_from_slice(&previous.to_be_bytes());
+        output.extend_from_slice(&chain.n.to_be_bytes());
+
+        let mut mk = chain.step::<AE>();
+        let start = output.len();
+        output.resize(start + input.len() + AE::TAG_LENGTH, 0);
+        let (header, ciphertext) = output.split_at_mut(start);
+        let result = seal::<AE>(&mk, header, ad, input, ciphertext);
+        zero(&mut mk);
+        result?;
+
+        Ok(output)
+    }
+
+    pub fn decrypt(&mut self, ad: &[u8], input: &[u8]) -> Result<Vec<u8>, Error> {
+        let len = header_length::<KEX>();
+        if input.len() < len + AE::TAG_LENGTH {
+            return Err(Error::Length(Length::Encoding));
+        }
+        let (header_bytes, ciphertext) = input.split_at(len);
+        let header = Header::<KEX>::from_bytes(header_bytes);
+        let epoch = epoch::<KEX>(&header.pk);
+        let mut output = vec![0; ciphertext.len() - AE::TAG_LENGTH];
+
+        // skipped message
+        if let Some(mk) = self.skipped.get(&(epoch, header.n)) {
+            open::<AE>(mk, header_bytes, ad, ciphertext, &mut output)?;
+            let mut mk = self.skipped.remove(&(epoch, header.n)).expect("key exists");
+            zero(&mut mk);
+            self.order.retain(|&id| id != (epoch, header.n));
+            return Ok(output);
+        }
+
+        let mut skipped = Vec::new();
+
+        // current epoch
+        if self.remote.as_ref().map(|&(_, e)| e == epoch).unwrap_or(false) {
+            let mut chain = self.recv.clone().ok_or(Error::InvalidState)?;
+            skip::<AE>(&mut chain, header.n, epoch, &mut skipped)?;
+            let mut mk = chain.step::<AE>();
+            let result = open::<AE>(&mk, header_bytes, ad, ciphertext, &mut output);
+            zero(&mut mk);
+            result?;
+
+            self.recv = Some(chain);
+            self.store(skipped);
+            return Ok(output);
+        }
+
+        // new epoch
+        let (sk, _) = self.keypair.as_ref().ok_or(Error::InvalidState)?;
+
+        if let (Some(chain), Some((_, remote))) = (self.recv.as_ref(), self.remote.as_ref()) {
+            let mut chain = chain.clone();
+            skip::<AE>(&mut chain, header.previous, *remote, &mut skipped)?;
+        }
+
+        let mut sharedkey: Vec<u8> = vec![0; KEX::SHARED_LENGTH];
+        <KEX as CheckedExchange>::exchange_from(&mut sharedkey, sk, &header.m)?;
+        let (root, key) = kdf_rk(&self.root, &sharedkey);
+        zero(&mut sharedkey);
+
+        let mut chain = Chain { key, n: 0 };
+        skip::<AE>(&mut chain, header.n, epoch, &mut skipped)?;
+        let mut mk = chain.step::<AE>();
+        let result = open::<AE>(&mk, header_bytes, ad, ciphertext, &mut output);
+        zero(&mut mk);
+        result?;
+
+        self.root = root;
+        self.remote = Some((header.pk, epoch));
+        self.recv = Some(chain);
+        self.previous = self.send.take().map(|(chain, _)| chain.n).unwrap_or(0);
+        self.store(skipped);
+        Ok(output)
+    }
+
+    /// Number of stored skipped message keys.
+    pub fn skipped_keys(&self) -> usize {
+        self.skipped.len()
+    }
+
+    fn store(&mut self, skipped: Vec<((Epoch, u32), Vec<u8>)>) {
+        for (id, mk) in skipped {
+            if let Some(mut old) = self.skipped.insert(id, mk) {
+                zero(&mut old);
+            } else {
+                self.order.push_back(id);
+            }
+        }
+
+        while self.order.len() > MAX_SKIPPED_KEYS {
+            if let Some(id) = self.order.pop_front() {
+                if let Some(mut mk) = self.skipped.remove(&id) {
+                    zero(&mut mk);
+                }
+            }
+        }
+    }
+}
+
+impl<KEX: KeyExchange, AE: AeadCipher> Drop for Ratchet<KEX, AE> {
+    fn drop(&mut self) {
+        zero(&mut self.root);
+        for mk in self.skipped.values_mut() {
+            zero(mk);
+        }
+    }
+}
+
+impl Chain {
+    /// Advance the chain, returns the message key.
+    fn step<AE: AeadCipher>(&mut self) -> Vec<u8> {
+        let mut mk = vec![0; AE::KEY_LENGTH];
+        let kdf = NorxKdf::from_prk(&self.key);
+        kdf.expand(b"chain", &mut self.key);
+        kdf.expand(b"message", &mut mk);
+        self.n += 1;
+        mk
+    }
+}
+
+impl Drop for Chain {
+    fn drop(&mut self) {
+        zero(&mut self.key);
+    }
+}
+
+impl<KEX: KeyExchange> Header<KEX> {
+    /// `input` must be `header_length` long.
+    fn from_bytes(input: &[u8]) -> Self {
+        let (pk, input) = input.split_at(KEX::PublicKey::BYTES_LENGTH);
+        let (m, input) = input.split_at(KEX::Message::BYTES_LENGTH);
+        let mut previous = [0; 4];
+        let mut n = [0; 4];
+        previous.copy_from_slice(&input[..4]);
+        n.copy_from_slice(&input[4..8]);
+
+        Header {
+            pk: KEX::PublicKey::from_bytes(pk),
+            m: KEX::Message::from_bytes(m),
+            previous: u32::from_be_bytes(previous),
+            n: u32::from_be_bytes(n)
+        }
+    }
+}
+
+
+fn header_length<KEX: KeyExchange>() -> usize {
+    KEX::PublicKey::BYTES_LENGTH + KEX::Message::BYTES_LENGTH + 8
+}
+
+fn epoch<KEX: KeyExchange>(pk: &KEX::PublicKey) -> Epoch {
+    let mut epoch = [0; 32];
+    pk.read_bytes(|pk| NorxHash::digest(pk, &mut epoch));
+    epoch
+}
+
+fn kdf_rk(root: &[u8], sharedkey: &[u8]) -> ([u8; ROOT_KEY_LENGTH], [u8; KEY_LENGTH]) {
+    let mut next = [0; ROOT_KEY_LENGTH];
+    let mut chain = [0; KEY_LENGTH];
+    let kdf = NorxKdf::extract(root, sharedkey);
+    kdf.expand(b"sarkara ratchet root", &mut next);
+    kdf.expand(b"sarkara ratchet chain", &mut chain);
+    (next, chain)
+}
+
+/// Advance `chain` to `n`, keeping the keys in between.
+fn skip<AE: AeadCipher>(chain: &mut Chain, n: u32, epoch: Epoch, skipped: &mut Vec<((Epoch, u32), Vec<u8>)>)
+    -> Result<(), Error>
+{
+    if n < chain.n || n - chain.n > MAX_SKIP {
+        return Err(Error::InvalidState);
+    }
+
+    while chain.n < n {
+        let i = chain.n;
+        skipped.push(((epoch, i), chain.step::<AE>()));
+    }
+
+    Ok(())
+}
+
+fn seal<AE: AeadCipher>(mk: &[u8], header: &[u8], ad: &[u8], input: &[u8], output: &mut [u8]) -> Result<(), Error> {
+    let nonce = vec![0; AE::NONCE_LENGTH];
+    AE::new(mk).seal(&nonce, &aad(header, ad), input, output)
+}
+
+fn open<AE: AeadCipher>(mk: &[u8], header: &[u8], ad: &[u8], input: &[u8], output: &mut [u8]) -> Result<(), Error> {
+    let nonce = vec![0; AE::NONCE_LENGTH];
+    AE::new(mk).open(&nonce, &aad(header, ad), input, output)
+}
+
+/// The header is fixed length.
+fn aad(header: &[u8], ad: &[u8]) -> Vec<u8> {
+    let mut aad = Vec::with_capacity(header.len() + ad.len());
+    aad.extend_from_slice(header);
+    aad.extend_from_slice(ad);
+    aad
+}
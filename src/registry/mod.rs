@@ -0,0 +1,317 @@
+//! Algorithms chosen at runtime.
+//!
+//! `KeyExchange`, `Signature` and `AeadCipher` are not object safe,
+//! the `Dyn*` traits wrap them over byte-encoded keys.
+
+use core::fmt;
+use core::str::FromStr;
+use core::marker::PhantomData;
+use alloc::vec;
+use alloc::vec::Vec;
+use rand::{ RngCore, CryptoRng };
+use crate::aead::{ AeadCipher, norx6441::Norx6441, norx_mrs::NorxMRS };
+use crate::kex::{ CheckedExchange, kyber::Kyber };
+use crate::sign::{ Signature, dilithium::Dilithium };
+use crate::{ Packing, Error, Length, Decoding };
+
+
+/// Stable identifiers, never reuse a retired one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Algorithm {
+    Kyber = 1,
+    Dilithium = 2,
+    Norx6441 = 3,
+    NorxMRS = 4,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Kind {
+    KeyExchange,
+    Signature,
+    Aead,
+}
+
+impl Algorithm {
+    pub const ALL: &'static [Algorithm] = &[
+        Algorithm::Kyber,
+        Algorithm::Dilithium,
+        Algorithm::Norx6441,
+        Algorithm::NorxMRS
+    ];
+
+    pub fn id(self) -> u16 {
+        self as u16
+    }
+
+    pub fn from_id(id: u16) -> Option<Algorithm> {
+        Algorithm::ALL.iter()
+            .find(|alg| alg.id() == id)
+            .cloned()
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Algorithm::Kyber => "kyber",
+            Algorithm::Dilithium => "dilithium",
+            Algorithm::Norx6441 => "norx6441",
+            Algorithm::NorxMRS => "norxmrs"
+        }
+    }
+
+    pub fn kind(self) -> Kind {
+        match self {
+            Algorithm::Kyber => Kind::KeyExchange,
+            Algorithm::Dilithium => Kind::Signature,
+            Algorithm::Norx6441 | Algorithm::NorxMRS => Kind::Aead
+        }
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = Error;
+
+    /// Names are matched case-insensitively.
+    fn from_str(name: &str) -> Result<Algorithm, Error> {
+        Algorithm::ALL.iter()
+            .find(|alg| alg.name().eq_ignore_ascii_case(name))
+            .cloned()
+            .ok_or(Error::Decoding(Decoding::Algorithm))
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+
+/// Types with a registered algorithm identifier.
+pub trait Identified {
+    const ALGORITHM: Algorithm;
+}
+
+impl Identified for Kyber {
+    const ALGORITHM: Algorithm = Algorithm::Kyber;
+}
+
+impl Identified for Dilithium {
+    const ALGORITHM: Algorithm = Algorithm::Dilithium;
+}
+
+impl Identified for Norx6441 {
+    const ALGORITHM: Algorithm = Algorithm::Norx6441;
+}
+
+impl Identified for NorxMRS {
+    const ALGORITHM: Algorithm = Algorithm::NorxMRS;
+}
+
+
+/// A cryptographic rng usable as a trait object.
+pub trait CryptoRngCore: RngCore + CryptoRng {}
+
+impl<R: RngCore + CryptoRng> CryptoRngCore for R {}
+
+pub trait DynKeyExchange: Sync {
+    fn algorithm(&self) -> Algorithm;
+    fn private_key_length(&self) -> usize;
+    fn public_key_length(&self) -> usize;
+    fn message_length(&self) -> usize;
+    fn shared_length(&self) -> usize;
+
+    /// Returns `(private key, public key)`.
+    fn keypair(&self, r: &mut dyn CryptoRngCore) -> (Vec<u8>, Vec<u8>);
+    fn exchange_to(&self, r: &mut dyn CryptoRngCore, sharedkey: &mut [u8], pk: &[u8]) -> Result<Vec<u8>, Error>;
+    fn exchange_from(&self, sharedkey: &mut [u8], sk: &[u8], m: &[u8]) -> Result<(), Error>;
+    fn public_key(&self, sk: &[u8]) -> Result<Vec<u8>, Error>;
+}
+
+pub trait DynSignature: Sync {
+    fn algorithm(&self) -> Algorithm;
+    fn private_key_length(&self) -> usize;
+    fn public_key_length(&self) -> usize;
+    fn signature_length(&self) -> usize;
+
+    /// Returns `(private key, public key)`.
+    fn keypair(&self, r: &mut dyn CryptoRngCore) -> (Vec<u8>, Vec<u8>);
+    fn signature(&self, r: &mut dyn CryptoRngCore, sk: &[u8], data: &[u8]) -> Result<Vec<u8>, Error>;
+    fn verify(&self, pk: &[u8], sig: &[u8], data: &[u8]) -> Result<(), Error>;
+    fn public_key(&self, sk: &[u8]) -> Result<Vec<u8>, Error>;
+}
+
+pub trait DynAead: Sync {
+    fn algorithm(&self) -> Algorithm;
+    fn key_length(&self) -> usize;
+    fn nonce_length(&self) -> usize;
+    fn tag_length(&self) -> usize;
+
+    fn seal(&self, key: &[u8], nonce: &[u8], aad: &[u8], input: &[u8], output: &mut [u8]) -> Result<(), Error>;
+    fn open(&self, key: &[u8], nonce: &[u8], aad: &[u8], input: &[u8], output: &mut [u8]) -> Result<(), Error>;
+}
+
+
+pub fn key_exchange(alg: Algorithm) -> Option<&'static dyn DynKeyExchange> {
+    match alg {
+        Algorithm::Kyber => Some(&KYBER),
+        _ => None
+    }
+}
+
+pub fn signature(alg: Algorithm) -> Option<&'static dyn DynSignature> {
+    match alg {
+        Algorithm::Dilithium => Some(&DILITHIUM),
+        _ => None
+    }
+}
+
+pub fn aead(alg: Algorithm) -> Option<&'static dyn DynAead> {
+    match alg {
+        Algorithm::Norx6441 => Some(&NORX6441),
+        Algorithm::NorxMRS => Some(&NORXMRS),
+        _ => None
+    }
+}
+
+
+struct Dyn<T>(Algorithm, PhantomData<fn() -> T>);
+
+static KYBER: Dyn<Kyber> = Dyn(Algorithm::Kyber, PhantomData);
+static DILITHIUM: Dyn<Dilithium> = Dyn(Algorithm::Dilithium, PhantomData);
+static NORX6441: Dyn<Norx6441> = Dyn(Algorithm::Norx6441, PhantomData);
+static NORXMRS: Dyn<NorxMRS> = Dyn(Algorithm::NorxMRS, PhantomData);
+
+impl<KEX: CheckedExchange + 'static> DynKeyExchange for Dyn<KEX> {
+    fn algorithm(&self) -> Algorithm {
+        self.0
+    }
+
+    fn private_key_length(&self) -> usize {
+        KEX::PrivateKey::BYTES_LENGTH
+    }
+
+    fn public_key_length(&self) -> usize {
+        KEX::PublicKey::BYTES_LENGTH
+    }
+
+    fn message_length(&self) -> usize {
+        KEX::Message::BYTES_LENGTH
+    }
+
+    fn shared_length(&self) -> usize {
+        KEX::SHARED_LENGTH
+    }
+
+    fn keypair(&self, r: &mut dyn CryptoRngCore) -> (Vec<u8>, Vec<u8>) {
+        let (sk, pk) = KEX::keypair(r);
+        (to_vec(&sk), to_vec(&pk))
+    }
+
+    fn exchange_to(&self, r: &mut dyn CryptoRngCore, sharedkey: &mut [u8], pk: &[u8]) -> Result<Vec<u8>, Error> {
+        let pk = from_bytes::<KEX::PublicKey>(pk, Length::Key)?;
+        check(sharedkey, KEX::SHARED_LENGTH, Length::Output)?;
+        Ok(to_vec(&KEX::exchange_to(r, sharedkey, &pk)))
+    }
+
+    fn exchange_from(&self, sharedkey: &mut [u8], sk: &[u8], m: &[u8]) -> Result<(), Error> {
+        let sk = from_bytes::<KEX::PrivateKey>(sk, Length::Key)?;
+        let m = from_bytes::<KEX::Message>(m, Length::Encoding)?;
+        check(sharedkey, KEX::SHARED_LENGTH, Length::Output)?;
+        <KEX as CheckedExchange>::exchange_from(sharedkey, &sk, &m)
+    }
+
+    fn public_key(&self, sk: &[u8]) -> Result<Vec<u8>, Error> {
+        let sk = from_bytes::<KEX::PrivateKey>(sk, Length::Key)?;
+        Ok(to_vec(&KEX::public_key(&sk)))
+    }
+}
+
+impl<SS: Signature + 'static> DynSignature for Dyn<SS> {
+    fn algorithm(&self) -> Algorithm {
+        self.0
+    }
+
+    fn private_key_length(&self) -> usize {
+        SS::PrivateKey::BYTES_LENGTH
+    }
+
+    fn public_key_length(&self) -> usize {
+        SS::PublicKey::BYTES_LENGTH
+    }
+
+    fn signature_length(&self) -> usize {
+        SS::Signature::BYTES_LENGTH
+    }
+
+    fn keypair(&self, r: &mut dyn CryptoRngCore) -> (Vec<u8>, Vec<u8>) {
+        let (sk, pk) = SS::keypair(r);
+        (to_vec(&sk), to_vec(&pk))
+    }
+
+    fn signature(&self, r: &mut dyn CryptoRngCore, sk: &[u8], data: &[u8]) -> Result<Vec<u8>, Error> {
+        let sk = from_bytes::<SS::PrivateKey>(sk, Length::Key)?;
+        Ok(to_vec(&SS::signature(r, &sk, data)))
+    }
+
+    fn verify(&self, pk: &[u8], sig: &[u8], data: &[u8]) -> Result<(), Error> {
+        let pk = from_bytes::<SS::PublicKey>(pk, Length::Key)?;
+        let sig = from_bytes::<SS::Signature>(sig, Length::Encoding)?;
+        SS::verify(&pk, &sig, data)
+    }
+
+    fn public_key(&self, sk: &[u8]) -> Result<Vec<u8>, Error> {
+        let sk = from_bytes::<SS::PrivateKey>(sk, Length::Key)?;
+        Ok(to_vec(&SS::public_key(&sk)))
+    }
+}
+
+impl<AE: AeadCipher + 'static> DynAead for Dyn<AE> {
+    fn algorithm(&self) -> Algorithm {
+        self.0
+    }
+
+    fn key_length(&self) -> usize {
+        AE::KEY_LENGTH
+    }
+
+    fn nonce_length(&self) -> usize {
+        AE::NONCE_LENGTH
+    }
+
+    fn tag_length(&self) -> usize {
+        AE::TAG_LENGTH
+    }
+
+    fn seal(&self, key: &[u8], nonce: &[u8], aad: &[u8], input: &[u8], output: &mut [u8]) -> Result<(), Error> {
+        check(key, AE::KEY_LENGTH, Length::Key)?;
+        check(nonce, AE::NONCE_LENGTH, Length::Nonce)?;
+        AE::new(key).seal(nonce, aad, input, output)
+    }
+
+    fn open(&self, key: &[u8], nonce: &[u8], aad: &[u8], input: &[u8], output: &mut [u8]) -> Result<(), Error> {
+        check(key, AE::KEY_LENGTH, Length::Key)?;
+        check(nonce, AE::NONCE_LENGTH, Length::Nonce)?;
+        AE::new(key).open(nonce, aad, input, output)
+    }
+}
+
+
+fn check(buf: &[u8], len: usize, err: Length) -> Result<(), Error> {
+    if buf.len() == len {
+        Ok(())
+    } else {
+        Err(Error::Length(err))
+    }
+}
+
+fn from_bytes<T: Packing>(buf: &[u8], err: Length) -> Result<T, Error> {
+    check(buf, T::BYTES_LENGTH, err)?;
+    Ok(T::from_bytes(buf))
+}
+
+fn to_vec<T: Packing>(t: &T) -> Vec<u8> {
+    let mut buf = vec![0; T::BYTES_LENGTH];
+    t.read_bytes(|bytes| buf.copy_from_slice(bytes));
+    buf
+}
@@ -1,44 +1,67 @@
-use std::marker::PhantomData;
+use core::marker::PhantomData;
+use alloc::vec;
+use alloc::vec::Vec;
 use rand::{ Rng, CryptoRng };
 use seckey::TempKey;
 use crate::kex::{ KeyExchange, CheckedExchange };
 use crate::aead::{ AeadCipher, Online };
-use crate::Error;
+use crate::kdf::{ Kdf, norx::NorxKdf };
+use crate::fingerprint::{ ShortAuthString, SAS_LENGTH };
+use crate::{ Packing, Error };
 
 
+const SALT: &[u8] = b"sarkara sealedbox";
+
+/// The sender is anonymous.
+///
+/// There is no sender authenticated variant, a KEM alone can not authenticate
+/// the sender without interaction. Sign the message if the sender must be known.
 pub struct SealedBox<KEX, AE>(PhantomData<(KEX, AE)>);
-pub struct Sealing<AE: AeadCipher>(AE);
-pub struct Opening<AE: AeadCipher>(AE);
+pub struct Sealing<AE: AeadCipher>(AE, ShortAuthString);
+pub struct Opening<AE: AeadCipher>(AE, ShortAuthString);
 
 
 impl<KEX, AE> SealedBox<KEX, AE>
     where
         KEX: KeyExchange,
-        AE: AeadCipher,
-//        AE::KEY_LENGTH = KEX::SHARED_LENGTH
+        AE: AeadCipher
 {
     pub fn send<R: Rng + CryptoRng>(r: R, pk: &KEX::PublicKey) -> (KEX::Message, Sealing<AE>) {
-        // TODO static assert
-        assert_eq!(KEX::SHARED_LENGTH, AE::KEY_LENGTH);
         let mut sharedkey: Vec<u8> = vec![0; KEX::SHARED_LENGTH];
         let mut sharedkey = TempKey::from(&mut sharedkey as &mut [u8]);
 
         let m = KEX::exchange_to(r, &mut sharedkey, pk);
-        let ae = AE::new(&sharedkey);
+        let (ae, sas) = Self::derive(&sharedkey, pk, &m);
 
-        (m, Sealing(ae))
+        (m, Sealing(ae, sas))
     }
 
     pub fn recv(sk: &KEX::PrivateKey, m: &KEX::Message) -> Opening<AE> {
-        // TODO static assert
-        assert_eq!(KEX::SHARED_LENGTH, AE::KEY_LENGTH);
         let mut sharedkey: Vec<u8> = vec![0; KEX::SHARED_LENGTH];
         let mut sharedkey = TempKey::from(&mut sharedkey as &mut [u8]);
 
         KEX::exchange_from(&mut sharedkey, sk, m);
-        let ae = AE::new(&sharedkey);
+        let (ae, sas) = Self::derive(&sharedkey, &KEX::public_key(sk), m);
+
+        Opening(ae, sas)
+    }
+
+    fn derive(sharedkey: &[u8], pk: &KEX::PublicKey, m: &KEX::Message) -> (AE, ShortAuthString) {
+        let mut key: Vec<u8> = vec![0; AE::KEY_LENGTH];
+        let mut key = TempKey::from(&mut key as &mut [u8]);
+        let mut sas = [0; SAS_LENGTH];
+
+        let kdf = NorxKdf::extract(SALT, sharedkey);
+        m.read_bytes(|m| kdf.expand(m, &mut key));
+
+        // sas binds the whole transcript
+        let mut info = Vec::with_capacity(3 + KEX::PublicKey::BYTES_LENGTH + KEX::Message::BYTES_LENGTH);
+        info.extend_from_slice(b"sas");
+        pk.read_bytes(|pk| info.extend_from_slice(pk));
+        m.read_bytes(|m| info.extend_from_slice(m));
+        kdf.expand(&info, &mut sas);
 
-        Opening(ae)
+        (AE::new(&key), ShortAuthString::new(sas))
     }
 }
 
@@ -48,15 +71,13 @@ impl<KEX, AE> SealedBox<KEX, AE>
         AE: AeadCipher
 {
     pub fn checked_recv(sk: &KEX::PrivateKey, m: &KEX::Message) -> Result<Opening<AE>, Error> {
-        // TODO static assert
-        assert_eq!(KEX::SHARED_LENGTH, AE::KEY_LENGTH);
         let mut sharedkey: Vec<u8> = vec![0; KEX::SHARED_LENGTH];
         let mut sharedkey = TempKey::from(&mut sharedkey as &mut [u8]);
 
         <KEX as CheckedExchange>::exchange_from(&mut sharedkey, sk, m)?;
-        let ae = AE::new(&sharedkey);
+        let (ae, sas) = Self::derive(&sharedkey, &KEX::public_key(sk), m);
 
-        Ok(Opening(ae))
+        Ok(Opening(ae, sas))
     }
 }
 
@@ -65,6 +86,11 @@ impl<AE: AeadCipher> Sealing<AE> {
     pub fn seal(&self, nonce: &[u8], aad: &[u8], input: &[u8], output: &mut [u8]) -> Result<(), Error> {
         self.0.seal(nonce, aad, input, output)
     }
+
+    #[inline]
+    pub fn sas(&self) -> &ShortAuthString {
+        &self.1
+    }
 }
 
 impl<'a, AE: AeadCipher + Online<'a>> Sealing<AE> {
@@ -79,6 +105,11 @@ impl<AE: AeadCipher> Opening<AE> {
     pub fn open(&self, nonce: &[u8], aad: &[u8], input: &[u8], output: &mut [u8]) -> Result<(), Error> {
         self.0.open(nonce, aad, input, output)
     }
+
+    #[inline]
+    pub fn sas(&self) -> &ShortAuthString {
+        &self.1
+    }
 }
 
 impl<'a, AE: AeadCipher + Online<'a>> Opening<AE> {
@@ -1,11 +1,43 @@
-use rand::{ Rng, CryptoRng };
+use alloc::vec::Vec;
+use arrayref::{ array_ref, mut_array_refs };
+use rand::{ Rng, CryptoRng, SeedableRng };
 use dilithium::{ params, sign };
-use crate::{ Packing, Error };
-use super::{ Signature, DeterministicSignature };
+use crate::hash::{ Xof, XofReader, norx::NorxHash };
+use crate::kdf::{ Kdf, norx::NorxKdf };
+use crate::rand::norx::NorxRng;
+use crate::pkcs8::AlgorithmIdentifier;
+use crate::common::eq_ct;
+use crate::{ Packing, Error, Length, SEED_LENGTH };
+use super::{
+    Signature, DeterministicSignature, SeedableSignature,
+    ParallelVerify, ContextSignature, PrehashSignature,
+    MAX_CONTEXT_LENGTH, check_context
+};
 
 
+// sk || pk, the `dilithium` secret key does not embed the whole public key.
+//
+// NOTE this changed the private key encoding, keys encoded before 0.10
+// (`sk` only) are migrated with `PrivateKey::from_legacy`.
+const SECRETKEYBYTES: usize = params::SECRETKEYBYTES + params::PUBLICKEYBYTES;
+
+// The signed data is framed as in FIPS 204, `0x00 || len(ctx) || ctx || message` or
+// `0x01 || len(ctx) || ctx || len(PREHASH_ID) || PREHASH_ID || digest`,
+// the plain methods use the empty context.
+// `PREHASH_ID` stands in for the hash OID, NORX hash has none.
+//
+// NOTE versions before 0.10 signed the message as is,
+// such signatures only verify with `Dilithium::verify_legacy`.
+const PURE: u8 = 0x00;
+const PREHASH: u8 = 0x01;
+const PREHASH_ID: &[u8] = b"sarkara norx hash";
+const PREHASH_LENGTH: usize = 64;
+const PREHASH_FRAME_LENGTH: usize = 3 + MAX_CONTEXT_LENGTH + PREHASH_ID.len() + PREHASH_LENGTH;
+
+const CHECK_MESSAGE: &[u8] = b"sarkara check keypair";
+
 pub struct Dilithium;
-pub struct PrivateKey([u8; params::SECRETKEYBYTES]);
+pub struct PrivateKey([u8; SECRETKEYBYTES], Option<[u8; SEED_LENGTH]>);
 pub struct PublicKey([u8; params::PUBLICKEYBYTES]);
 pub struct SignatureData([u8; params::BYTES]);
 
@@ -15,47 +47,188 @@ impl Signature for Dilithium {
     type Signature = SignatureData;
 
     fn keypair<R: Rng + CryptoRng>(mut r: R) -> (Self::PrivateKey, Self::PublicKey) {
-        let mut sk = [0; params::SECRETKEYBYTES];
-        let mut pk = [0; params::PUBLICKEYBYTES];
-        sign::keypair(&mut r, &mut pk, &mut sk);
-        (PrivateKey(sk), PublicKey(pk))
+        let mut seed = [0; SEED_LENGTH];
+        r.fill_bytes(&mut seed);
+        Dilithium::keypair_from_seed(&seed)
     }
 
     fn signature<R: Rng + CryptoRng>(_: R, sk: &Self::PrivateKey, data: &[u8]) -> Self::Signature {
         <Dilithium as DeterministicSignature>::signature(sk, data)
     }
 
-    fn verify(
-        &PublicKey(ref pk): &Self::PublicKey,
-        &SignatureData(ref sig): &Self::Signature,
-        data: &[u8]
-    ) -> Result<(), Error> {
-        if sign::verify(data, sig, pk) {
-            Ok(())
-        } else {
-            Err(Error::VerificationFailed)
+    fn verify(pk: &Self::PublicKey, sig: &Self::Signature, data: &[u8]) -> Result<(), Error> {
+        verify_data(pk, sig, &frame(&[], data))
+    }
+
+    fn public_key(PrivateKey(sk, _): &Self::PrivateKey) -> Self::PublicKey {
+        PublicKey(*array_ref!(sk, params::SECRETKEYBYTES, params::PUBLICKEYBYTES))
+    }
+
+    /// Also checks the secret part by signing a fixed message.
+    fn check_keypair(sk: &Self::PrivateKey, pk: &Self::PublicKey) -> Result<(), Error> {
+        if !eq_ct(&Dilithium::public_key(sk).0, &pk.0) {
+            return Err(Error::KeyMismatch);
         }
+
+        let sig = <Dilithium as DeterministicSignature>::signature(sk, CHECK_MESSAGE);
+        Dilithium::verify(pk, &sig, CHECK_MESSAGE)
+            .map_err(|_| Error::KeyMismatch)
     }
 }
 
 impl DeterministicSignature for Dilithium {
-    fn signature(&PrivateKey(ref sk): &Self::PrivateKey, data: &[u8]) -> Self::Signature {
-        let mut sig = [0; params::BYTES];
-        sign::sign(&mut sig, data, &sk);
-        SignatureData(sig)
+    fn signature(sk: &Self::PrivateKey, data: &[u8]) -> Self::Signature {
+        sign_data(sk, &frame(&[], data))
+    }
+}
+
+impl ParallelVerify for Dilithium {}
+
+impl ContextSignature for Dilithium {
+    fn signature_with_context(sk: &Self::PrivateKey, ctx: &[u8], data: &[u8]) -> Result<Self::Signature, Error> {
+        check_context(ctx)?;
+        Ok(sign_data(sk, &frame(ctx, data)))
+    }
+
+    fn verify_with_context(pk: &Self::PublicKey, sig: &Self::Signature, ctx: &[u8], data: &[u8]) -> Result<(), Error> {
+        check_context(ctx)?;
+        verify_data(pk, sig, &frame(ctx, data))
+    }
+}
+
+impl PrehashSignature for Dilithium {
+    type Hash = NorxHash;
+
+    fn signature_prehashed(sk: &Self::PrivateKey, ctx: &[u8], hash: Self::Hash) -> Result<Self::Signature, Error> {
+        check_context(ctx)?;
+        let mut buf = [0; PREHASH_FRAME_LENGTH];
+        Ok(sign_data(sk, frame_prehashed(&mut buf, ctx, hash)))
+    }
+
+    fn verify_prehashed(pk: &Self::PublicKey, sig: &Self::Signature, ctx: &[u8], hash: Self::Hash) -> Result<(), Error> {
+        check_context(ctx)?;
+        let mut buf = [0; PREHASH_FRAME_LENGTH];
+        verify_data(pk, sig, frame_prehashed(&mut buf, ctx, hash))
+    }
+}
+
+impl SeedableSignature for Dilithium {
+    fn keypair_from_seed(seed: &[u8; SEED_LENGTH]) -> (Self::PrivateKey, Self::PublicKey) {
+        let mut sk = [0; SECRETKEYBYTES];
+
+        // NOTE the keypair depends on how `dilithium` consumes the rng,
+        // so the `dilithium` version is pinned.
+        let mut rngseed = [0; SEED_LENGTH];
+        NorxKdf::derive(b"sarkara seed", seed, b"dilithium keypair", &mut rngseed);
+        let mut rng = NorxRng::from_seed(rngseed);
+
+        {
+            let (sk, pk) = mut_array_refs!(&mut sk, params::SECRETKEYBYTES, params::PUBLICKEYBYTES);
+            sign::keypair(&mut rng, pk, sk);
+        }
+
+        let pk = *array_ref!(sk, params::SECRETKEYBYTES, params::PUBLICKEYBYTES);
+        (PrivateKey(sk, Some(*seed)), PublicKey(pk))
+    }
+}
+
+impl AlgorithmIdentifier for Dilithium {
+    type PrivateKey = PrivateKey;
+    type PublicKey = PublicKey;
+
+    /// 2.25.316991036999128169988469511729626057660.2, round 1 Dilithium mode 2, `sk || pk`
+    const OID: &'static [u8] = &[
+        0x69, 0x83, 0xdc, 0xfa, 0xa0, 0xb1, 0xc9, 0xe2, 0xb2, 0xb2, 0xb9, 0x94, 0x9e, 0xbf, 0x89, 0xd0, 0xde, 0xb6, 0x97, 0x3c,
+        0x02
+    ];
+}
+
+/// `ctx` must be checked.
+fn frame(ctx: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut framed = Vec::with_capacity(2 + ctx.len() + data.len());
+    framed.push(PURE);
+    framed.push(ctx.len() as u8);
+    framed.extend_from_slice(ctx);
+    framed.extend_from_slice(data);
+    framed
+}
+
+/// `ctx` must be checked.
+fn frame_prehashed<'a>(buf: &'a mut [u8; PREHASH_FRAME_LENGTH], ctx: &[u8], hash: NorxHash) -> &'a [u8] {
+    let len = 3 + ctx.len() + PREHASH_ID.len() + PREHASH_LENGTH;
+    let (header, rest) = buf.split_at_mut(2);
+    header[0] = PREHASH;
+    header[1] = ctx.len() as u8;
+    let (ctx_buf, rest) = rest.split_at_mut(ctx.len());
+    ctx_buf.copy_from_slice(ctx);
+    rest[0] = PREHASH_ID.len() as u8;
+    let (id, rest) = rest[1..].split_at_mut(PREHASH_ID.len());
+    id.copy_from_slice(PREHASH_ID);
+    hash.xof().read(&mut rest[..PREHASH_LENGTH]);
+    &buf[..len]
+}
+
+fn sign_data(sk: &PrivateKey, data: &[u8]) -> SignatureData {
+    let mut sig = [0; params::BYTES];
+    sign::sign(&mut sig, data, array_ref!(sk.0, 0, params::SECRETKEYBYTES));
+    SignatureData(sig)
+}
+
+fn verify_data(pk: &PublicKey, sig: &SignatureData, data: &[u8]) -> Result<(), Error> {
+    if sign::verify(data, &sig.0, &pk.0) {
+        Ok(())
+    } else {
+        Err(Error::InvalidSignature)
+    }
+}
+
+impl Dilithium {
+    /// Verify a signature made before 0.10, over the unframed message.
+    ///
+    /// Such a signature is bound to no context and is not separated from prehashed signatures,
+    /// only use this for signatures that can not be made again.
+    pub fn verify_legacy(pk: &PublicKey, sig: &SignatureData, data: &[u8]) -> Result<(), Error> {
+        verify_data(pk, sig, data)
+    }
+}
+
+impl PrivateKey {
+    /// Restore a private key encoded before 0.10, which did not include the public key.
+    pub fn from_legacy(sk: &[u8], pk: &PublicKey) -> Result<PrivateKey, Error> {
+        if sk.len() != params::SECRETKEYBYTES {
+            return Err(Error::Length(Length::Key));
+        }
+
+        let mut buf = [0; SECRETKEYBYTES];
+        buf[..params::SECRETKEYBYTES].copy_from_slice(sk);
+        buf[params::SECRETKEYBYTES..].copy_from_slice(&pk.0);
+        let sk = PrivateKey(buf, None);
+
+        Dilithium::check_keypair(&sk, pk)?;
+        Ok(sk)
+    }
+
+    /// The seed this key was generated from, if known.
+    ///
+    /// Keys restored by `Packing::from_bytes` do not carry a seed.
+    pub fn seed(&self) -> Option<&[u8; SEED_LENGTH]> {
+        self.1.as_ref()
     }
 }
 
 eq!(PrivateKey);
 eq!(PublicKey);
 eq!(SignatureData);
-packing!(PrivateKey; params::SECRETKEYBYTES);
+packing!(PrivateKey; SECRETKEYBYTES; None);
 packing!(PublicKey; params::PUBLICKEYBYTES);
 packing!(SignatureData; params::BYTES);
+armored!(PrivateKey; "SARKARA DILITHIUM PRIVATE KEY");
+armored!(PublicKey; "SARKARA DILITHIUM PUBLIC KEY");
+armored!(SignatureData; "SARKARA DILITHIUM SIGNATURE");
 
 #[cfg(feature = "serde")]
 mod serde1 {
-    use std::fmt;
+    use core::fmt;
     use serde::{
         Serialize, Serializer, Deserialize, Deserializer,
         de::{ self, Visitor }
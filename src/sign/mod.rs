@@ -1,7 +1,17 @@
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 use rand::{ Rng, CryptoRng };
-use crate::{ Packing, Error };
+use crate::common::eq_ct;
+use crate::hash::Hash;
+use crate::{ Packing, Error, Length, SEED_LENGTH };
 
-pub mod dilithium;
+/// Needs `alloc`, the signed data is framed.
+#[cfg(feature = "alloc")] pub mod dilithium;
+
+
+pub const MAX_CONTEXT_LENGTH: usize = 255;
 
 
 pub trait Signature {
@@ -14,8 +24,166 @@ pub trait Signature {
     fn signature<R: Rng + CryptoRng>(r: R, sk: &Self::PrivateKey, data: &[u8]) -> Self::Signature;
 
     fn verify(pk: &Self::PublicKey, sig: &Self::Signature, data: &[u8]) -> Result<(), Error>;
+
+    fn public_key(sk: &Self::PrivateKey) -> Self::PublicKey;
+
+    /// Check that the private key and public key belong together.
+    fn check_keypair(sk: &Self::PrivateKey, pk: &Self::PublicKey) -> Result<(), Error> {
+        let pk2 = Self::public_key(sk);
+        if pk.read_bytes(|x| pk2.read_bytes(|y| eq_ct(x, y))) {
+            Ok(())
+        } else {
+            Err(Error::KeyMismatch)
+        }
+    }
 }
 
 pub trait DeterministicSignature: Signature {
     fn signature(sk: &Self::PrivateKey, data: &[u8]) -> Self::Signature;
 }
+
+pub trait SeedableSignature: Signature {
+    /// Deterministically generate a keypair, the private key keeps the seed.
+    fn keypair_from_seed(seed: &[u8; SEED_LENGTH]) -> (Self::PrivateKey, Self::PublicKey);
+}
+
+/// Verify many signatures, each one independently.
+///
+/// With the `parallel` feature the items are spread over all available threads,
+/// otherwise this is the same as calling `verify` in a loop.
+///
+/// Not called `BatchVerify`, Dilithium has no batch verification
+/// that is cheaper than checking every signature.
+///
+/// TODO reuse the expanded matrix of a repeated public key,
+/// `dilithium` keeps the matrix expansion private.
+#[cfg(feature = "alloc")]
+pub trait ParallelVerify: Signature {
+    /// One result per item, in order.
+    fn verify_each(items: &[(&Self::PublicKey, &Self::Signature, &[u8])]) -> Vec<Result<(), Error>>
+        where Self::PublicKey: Sync, Self::Signature: Sync
+    {
+        let verify = |&(pk, sig, data): &(&Self::PublicKey, &Self::Signature, &[u8])| Self::verify(pk, sig, data);
+
+        #[cfg(feature = "parallel")]
+        let results = crate::common::par_map(items, verify);
+
+        #[cfg(not(feature = "parallel"))]
+        let results = items.iter().map(verify).collect();
+
+        results
+    }
+
+    /// `Ok` only if every item verifies.
+    fn verify_all(items: &[(&Self::PublicKey, &Self::Signature, &[u8])]) -> Result<(), Error>
+        where Self::PublicKey: Sync, Self::Signature: Sync
+    {
+        Self::verify_each(items).into_iter().collect()
+    }
+}
+
+/// Bind a signature to the purpose it was made for.
+///
+/// A signature only verifies under the context it was made with,
+/// the plain `Signature` methods use the empty context.
+pub trait ContextSignature: Signature {
+    /// TODO should be `ctx: &[u8; ..= MAX_CONTEXT_LENGTH]`
+    fn signature_with_context(sk: &Self::PrivateKey, ctx: &[u8], data: &[u8]) -> Result<Self::Signature, Error>;
+    fn verify_with_context(pk: &Self::PublicKey, sig: &Self::Signature, ctx: &[u8], data: &[u8]) -> Result<(), Error>;
+}
+
+/// Sign the digest of a message instead of the message itself.
+///
+/// A prehashed signature never verifies as a signature over the message, and vice versa.
+pub trait PrehashSignature: Signature {
+    type Hash: Hash;
+
+    fn signature_prehashed(sk: &Self::PrivateKey, ctx: &[u8], hash: Self::Hash) -> Result<Self::Signature, Error>;
+    fn verify_prehashed(pk: &Self::PublicKey, sig: &Self::Signature, ctx: &[u8], hash: Self::Hash) -> Result<(), Error>;
+}
+
+
+/// Incremental signing, see `PrehashSignature`.
+pub struct Signer<'a, SS: PrehashSignature> {
+    sk: &'a SS::PrivateKey,
+    ctx: &'a [u8],
+    hash: SS::Hash
+}
+
+/// Incremental verification, see `PrehashSignature`.
+pub struct Verifier<'a, SS: PrehashSignature> {
+    pk: &'a SS::PublicKey,
+    ctx: &'a [u8],
+    hash: SS::Hash
+}
+
+impl<'a, SS: PrehashSignature> Signer<'a, SS> {
+    pub fn new(sk: &'a SS::PrivateKey) -> Self {
+        Signer { sk, ctx: &[], hash: SS::Hash::new() }
+    }
+
+    pub fn with_context(sk: &'a SS::PrivateKey, ctx: &'a [u8]) -> Result<Self, Error> {
+        check_context(ctx)?;
+        Ok(Signer { sk, ctx, hash: SS::Hash::new() })
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.hash.update(data);
+    }
+
+    pub fn finalize(self) -> SS::Signature {
+        SS::signature_prehashed(self.sk, self.ctx, self.hash)
+            .expect("context length is checked")
+    }
+}
+
+impl<'a, SS: PrehashSignature> Verifier<'a, SS> {
+    pub fn new(pk: &'a SS::PublicKey) -> Self {
+        Verifier { pk, ctx: &[], hash: SS::Hash::new() }
+    }
+
+    pub fn with_context(pk: &'a SS::PublicKey, ctx: &'a [u8]) -> Result<Self, Error> {
+        check_context(ctx)?;
+        Ok(Verifier { pk, ctx, hash: SS::Hash::new() })
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.hash.update(data);
+    }
+
+    pub fn finalize(self, sig: &SS::Signature) -> Result<(), Error> {
+        SS::verify_prehashed(self.pk, sig, self.ctx, self.hash)
+    }
+}
+
+pub(crate) fn check_context(ctx: &[u8]) -> Result<(), Error> {
+    if ctx.len() <= MAX_CONTEXT_LENGTH {
+        Ok(())
+    } else {
+        Err(Error::Length(Length::Context))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, SS: PrehashSignature> io::Write for Signer<'a, SS> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, SS: PrehashSignature> io::Write for Verifier<'a, SS> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -4,7 +4,7 @@ extern crate sarkara;
 use std::thread;
 use std::sync::mpsc::channel;
 use rand::{ Rng, RngCore, FromEntropy, ChaChaRng };
-use sarkara::Error;
+use sarkara::{ Error, Length };
 use sarkara::aead::{ AeadCipher, Online, Encryption, Decryption };
 use sarkara::aead::norx6441::Norx6441;
 use sarkara::aead::norx_mrs::NorxMRS;
@@ -32,13 +32,30 @@ fn test_aead<AE: AeadCipher>() {
 
         assert_eq!(pt, ot);
 
+        assert!(matches!(
+            cipher.seal(&nonce, &aad, &pt, &mut ct[1..]),
+            Err(Error::Length(Length::Output))
+        ));
+
         ct[i - 1] ^= 0x42;
-        assert!(if let Err(Error::VerificationFailed) = cipher.open(&nonce, &aad, &ct, &mut ot) {
-            true
-        } else {
-            false
-        });
+        assert!(matches!(cipher.open(&nonce, &aad, &ct, &mut ot), Err(Error::VerificationFailed)));
     }
+
+    // wrong key and nonce lengths
+    assert!(matches!(AE::try_new(&key[1..]), Err(Error::Length(Length::Key))));
+
+    let cipher = AE::try_new(&key).unwrap();
+    let pt = [0u8; 16];
+    let mut ct = vec![0u8; pt.len() + AE::TAG_LENGTH];
+    assert!(matches!(
+        cipher.seal(&nonce[1..], &[], &pt, &mut ct),
+        Err(Error::Length(Length::Nonce))
+    ));
+    let mut ot = [0u8; 16];
+    assert!(matches!(
+        cipher.open(&nonce[1..], &[], &ct, &mut ot),
+        Err(Error::Length(Length::Nonce))
+    ));
 }
 
 fn test_onlineae<AE>()
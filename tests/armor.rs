@@ -0,0 +1,63 @@
+extern crate rand;
+extern crate sarkara;
+
+use rand::{ FromEntropy, ChaChaRng };
+use sarkara::{ Error, Decoding };
+use sarkara::armor::{ self, Armored };
+use sarkara::kex::KeyExchange;
+use sarkara::sign::{ Signature, DeterministicSignature };
+use sarkara::kex::kyber::Kyber;
+use sarkara::sign::dilithium::Dilithium;
+
+
+fn test_armor<T: Armored, U: Armored>(t: &T) {
+    let text = armor::encode(t);
+    assert!(text.starts_with(&format!("-----BEGIN {}-----\n", T::LABEL)));
+    assert!(text.ends_with(&format!("-----END {}-----\n", T::LABEL)));
+    assert!(text.lines().all(|line| line.len() <= 64 || line.starts_with("-----")));
+
+    let t2: T = armor::decode(&text).unwrap();
+    assert!(t.read_bytes(|x| t2.read_bytes(|y| x == y)));
+
+    let t3: T = armor::decode(&text.replace('\n', "\r\n")).unwrap();
+    assert!(t.read_bytes(|x| t3.read_bytes(|y| x == y)));
+
+    // mislabeled
+    assert!(matches!(armor::decode::<U>(&text), Err(Error::Decoding(Decoding::Label))));
+
+    // corrupted
+    let mut bytes = text.into_bytes();
+    let pos = bytes.iter().position(|&b| b == b'\n').unwrap() + 10;
+    bytes[pos] = if bytes[pos] == b'A' { b'B' } else { b'A' };
+    let text = String::from_utf8(bytes).unwrap();
+    assert!(matches!(armor::decode::<T>(&text), Err(Error::Decoding(Decoding::Checksum))));
+
+    // truncated
+    let text = armor::encode(t);
+    let text = &text[..text.len() - 10];
+    assert!(armor::decode::<T>(text).is_err());
+}
+
+
+#[test]
+fn test_armor_kyber() {
+    let mut rng = ChaChaRng::from_entropy();
+    let mut sharedkey = [0; 32];
+    let (sk, pk) = Kyber::keypair(&mut rng);
+    let m = Kyber::exchange_to(&mut rng, &mut sharedkey, &pk);
+
+    test_armor::<_, <Kyber as KeyExchange>::PublicKey>(&sk);
+    test_armor::<_, <Kyber as KeyExchange>::PrivateKey>(&pk);
+    test_armor::<_, <Dilithium as Signature>::PublicKey>(&m);
+}
+
+#[test]
+fn test_armor_dilithium() {
+    let mut rng = ChaChaRng::from_entropy();
+    let (sk, pk) = Dilithium::keypair(&mut rng);
+    let sig = <Dilithium as DeterministicSignature>::signature(&sk, b"armor");
+
+    test_armor::<_, <Kyber as KeyExchange>::PrivateKey>(&sk);
+    test_armor::<_, <Kyber as KeyExchange>::PublicKey>(&pk);
+    test_armor::<_, <Dilithium as Signature>::PublicKey>(&sig);
+}
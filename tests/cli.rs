@@ -0,0 +1,89 @@
+#![cfg(feature = "cli")]
+
+extern crate rand;
+
+use std::{ env, fs };
+use std::ffi::OsStr;
+use std::path::PathBuf;
+use std::process::Command;
+use rand::{ RngCore, FromEntropy, ChaChaRng };
+
+
+fn sarkara<S: AsRef<OsStr>>(args: &[S]) -> i32 {
+    Command::new(env!("CARGO_BIN_EXE_sarkara"))
+        .args(args)
+        .output()
+        .unwrap()
+        .status
+        .code()
+        .unwrap()
+}
+
+fn tempdir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("sarkara-cli-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn test_cli(armor: &[&str]) {
+    let mut rng = ChaChaRng::from_entropy();
+    let dir = tempdir(if armor.is_empty() { "raw" } else { "armor" });
+    let path = |name: &str| dir.join(name).to_str().unwrap().to_owned();
+
+    let mut data = vec![0; 200_000];
+    rng.fill_bytes(&mut data);
+    fs::write(path("data"), &data).unwrap();
+
+    let args = |args: &[&str]| armor.iter().chain(args).map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+    // sign / verify
+    assert_eq!(sarkara(&args(&["keygen", "dilithium", &path("sign.sk"), &path("sign.pk")])), 0);
+    assert_eq!(sarkara(&args(&["sign", &path("sign.sk"), &path("data"), &path("data.sig")])), 0);
+    assert_eq!(sarkara(&["verify", &path("sign.pk"), &path("data"), &path("data.sig")]), 0);
+
+    fs::write(path("data2"), &data[1..]).unwrap();
+    assert_eq!(sarkara(&["verify", &path("sign.pk"), &path("data2"), &path("data.sig")]), 1);
+
+    // seal / open
+    assert_eq!(sarkara(&args(&["keygen", "kyber", &path("box.sk"), &path("box.pk")])), 0);
+    assert_eq!(sarkara(&["seal", &path("box.pk"), &path("data"), &path("data.box")]), 0);
+    assert_eq!(sarkara(&["open", &path("box.sk"), &path("data.box"), &path("data.out")]), 0);
+    assert_eq!(fs::read(path("data.out")).unwrap(), data);
+
+    // unverified plaintext is never written to stdout
+    assert_eq!(sarkara(&["open", &path("box.sk"), &path("data.box"), "-"]), 2);
+
+    let mut sealed = fs::read(path("data.box")).unwrap();
+    let last = sealed.len() - 1;
+    sealed[last] ^= 0x42;
+    fs::write(path("data.box"), &sealed).unwrap();
+    assert_eq!(sarkara(&["open", &path("box.sk"), &path("data.box"), &path("data.bad")]), 1);
+    assert!(!dir.join("data.bad").exists());
+    assert!(!dir.join("data.bad.tmp").exists());
+
+    // an existing output is left alone on failure
+    assert_eq!(sarkara(&["open", &path("box.sk"), &path("data.box"), &path("data.out")]), 1);
+    assert_eq!(fs::read(path("data.out")).unwrap(), data);
+    fs::write(path("data.box"), &sealed[..8]).unwrap();
+    assert_eq!(sarkara(&["open", &path("box.sk"), &path("data.box"), &path("data.out")]), 4);
+    assert_eq!(fs::read(path("data.out")).unwrap(), data);
+
+    // wrong key type, missing file, bad usage
+    assert_eq!(sarkara(&["verify", &path("box.pk"), &path("data"), &path("data.sig")]), 4);
+    assert_eq!(sarkara(&["verify", &path("sign.pk"), &path("missing"), &path("data.sig")]), 3);
+    assert_eq!(sarkara(&["keygen", "rsa"]), 2);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+
+#[test]
+fn test_raw() {
+    test_cli(&[]);
+}
+
+#[test]
+fn test_armor() {
+    test_cli(&["--armor"]);
+}
@@ -0,0 +1,124 @@
+#include <assert.h>
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "sarkara.h"
+
+
+static uint8_t *alloc(size_t len) {
+    uint8_t *buf = calloc(len ? len : 1, 1);
+    assert(buf != NULL);
+    return buf;
+}
+
+static void test_kyber(void) {
+    size_t sk_len = sarkara_kyber_private_key_bytes();
+    size_t pk_len = sarkara_kyber_public_key_bytes();
+    size_t m_len = sarkara_kyber_message_bytes();
+    size_t shared_len = sarkara_kyber_shared_bytes();
+    uint8_t *sk = alloc(sk_len), *pk = alloc(pk_len), *m = alloc(m_len);
+    uint8_t *shared = alloc(shared_len), *shared2 = alloc(shared_len);
+
+    assert(sarkara_kyber_keypair(sk, sk_len, pk, pk_len) == SARKARA_OK);
+    assert(sarkara_kyber_keypair(sk, sk_len - 1, pk, pk_len) == SARKARA_ERROR_LENGTH);
+    assert(sarkara_kyber_encapsulate(pk, pk_len, shared, shared_len, m, m_len) == SARKARA_OK);
+    assert(sarkara_kyber_decapsulate(sk, sk_len, m, m_len, shared2, shared_len) == SARKARA_OK);
+    assert(memcmp(shared, shared2, shared_len) == 0);
+
+    m[0] ^= 0x42;
+    assert(sarkara_kyber_decapsulate(sk, sk_len, m, m_len, shared2, shared_len) == SARKARA_ERROR_VERIFICATION_FAILED);
+    assert(sarkara_kyber_decapsulate(NULL, sk_len, m, m_len, shared2, shared_len) == SARKARA_ERROR_NULL_POINTER);
+
+    free(sk); free(pk); free(m); free(shared); free(shared2);
+}
+
+static void test_dilithium(void) {
+    size_t sk_len = sarkara_dilithium_private_key_bytes();
+    size_t pk_len = sarkara_dilithium_public_key_bytes();
+    size_t sig_len = sarkara_dilithium_signature_bytes();
+    uint8_t *sk = alloc(sk_len), *pk = alloc(pk_len), *sig = alloc(sig_len);
+    uint8_t msg[] = "Hello world!";
+
+    assert(sarkara_dilithium_keypair(sk, sk_len, pk, pk_len) == SARKARA_OK);
+    assert(sarkara_dilithium_sign(sk, sk_len, msg, sizeof msg, sig, sig_len) == SARKARA_OK);
+    assert(sarkara_dilithium_verify(pk, pk_len, msg, sizeof msg, sig, sig_len) == SARKARA_OK);
+
+    msg[0] ^= 0x42;
+    assert(sarkara_dilithium_verify(pk, pk_len, msg, sizeof msg, sig, sig_len) == SARKARA_ERROR_INVALID_SIGNATURE);
+    assert(sarkara_dilithium_verify(pk, pk_len, msg, sizeof msg, sig, sig_len + 1) == SARKARA_ERROR_LENGTH);
+
+    free(sk); free(pk); free(sig);
+}
+
+typedef int (*aead_fn)(
+    const uint8_t *, size_t,
+    const uint8_t *, size_t,
+    const uint8_t *, size_t,
+    const uint8_t *, size_t,
+    uint8_t *, size_t
+);
+
+static void test_aead(aead_fn seal, aead_fn open, size_t key_len, size_t nonce_len, size_t tag_len) {
+    uint8_t *key = alloc(key_len), *nonce = alloc(nonce_len);
+    uint8_t pt[33] = "Attack at dawn", ot[33];
+    uint8_t *ct = alloc(sizeof pt + tag_len);
+    uint8_t aad[] = "header";
+
+    memset(key, 0x11, key_len);
+    memset(nonce, 0x22, nonce_len);
+
+    assert(seal(key, key_len, nonce, nonce_len, aad, sizeof aad, pt, sizeof pt, ct, sizeof pt + tag_len) == SARKARA_OK);
+    assert(open(key, key_len, nonce, nonce_len, aad, sizeof aad, ct, sizeof pt + tag_len, ot, sizeof ot) == SARKARA_OK);
+    assert(memcmp(pt, ot, sizeof pt) == 0);
+
+    /* empty associated data may be NULL */
+    assert(seal(key, key_len, nonce, nonce_len, NULL, 0, pt, sizeof pt, ct, sizeof pt + tag_len) == SARKARA_OK);
+    assert(open(key, key_len, nonce, nonce_len, NULL, 0, ct, sizeof pt + tag_len, ot, sizeof ot) == SARKARA_OK);
+
+    ct[0] ^= 0x42;
+    assert(open(key, key_len, nonce, nonce_len, NULL, 0, ct, sizeof pt + tag_len, ot, sizeof ot) == SARKARA_ERROR_VERIFICATION_FAILED);
+    assert(seal(key, key_len - 1, nonce, nonce_len, NULL, 0, pt, sizeof pt, ct, sizeof pt + tag_len) == SARKARA_ERROR_LENGTH);
+    assert(seal(key, key_len, nonce, nonce_len - 1, NULL, 0, pt, sizeof pt, ct, sizeof pt + tag_len) == SARKARA_ERROR_LENGTH);
+    assert(open(key, key_len, nonce, nonce_len - 1, NULL, 0, ct, sizeof pt + tag_len, ot, sizeof ot) == SARKARA_ERROR_LENGTH);
+
+    free(key); free(nonce); free(ct);
+}
+
+static void test_sealedbox(void) {
+    size_t sk_len = sarkara_kyber_private_key_bytes();
+    size_t pk_len = sarkara_kyber_public_key_bytes();
+    size_t m_len = sarkara_kyber_message_bytes();
+    size_t nonce_len = sarkara_norx6441_nonce_bytes();
+    size_t tag_len = sarkara_norx6441_tag_bytes();
+    uint8_t *sk = alloc(sk_len), *pk = alloc(pk_len), *m = alloc(m_len);
+    uint8_t *nonce = alloc(nonce_len);
+    uint8_t pt[] = "sealed", ot[sizeof pt];
+    uint8_t *ct = alloc(sizeof pt + tag_len);
+
+    assert(sarkara_kyber_keypair(sk, sk_len, pk, pk_len) == SARKARA_OK);
+    assert(sarkara_sealedbox_send(pk, pk_len, m, m_len, nonce, nonce_len, NULL, 0, pt, sizeof pt, ct, sizeof pt + tag_len) == SARKARA_OK);
+    assert(sarkara_sealedbox_recv(sk, sk_len, m, m_len, nonce, nonce_len, NULL, 0, ct, sizeof pt + tag_len, ot, sizeof ot) == SARKARA_OK);
+    assert(memcmp(pt, ot, sizeof pt) == 0);
+
+    ct[0] ^= 0x42;
+    assert(sarkara_sealedbox_recv(sk, sk_len, m, m_len, nonce, nonce_len, NULL, 0, ct, sizeof pt + tag_len, ot, sizeof ot) == SARKARA_ERROR_VERIFICATION_FAILED);
+    assert(sarkara_sealedbox_send(pk, pk_len, m, m_len, nonce, nonce_len - 1, NULL, 0, pt, sizeof pt, ct, sizeof pt + tag_len) == SARKARA_ERROR_LENGTH);
+    assert(sarkara_sealedbox_recv(sk, sk_len, m, m_len, nonce, nonce_len - 1, NULL, 0, ct, sizeof pt + tag_len, ot, sizeof ot) == SARKARA_ERROR_LENGTH);
+
+    free(sk); free(pk); free(m); free(nonce); free(ct);
+}
+
+int main(void) {
+    test_kyber();
+    test_dilithium();
+    test_aead(sarkara_norx6441_seal, sarkara_norx6441_open,
+        sarkara_norx6441_key_bytes(), sarkara_norx6441_nonce_bytes(), sarkara_norx6441_tag_bytes());
+    test_aead(sarkara_norxmrs_seal, sarkara_norxmrs_open,
+        sarkara_norxmrs_key_bytes(), sarkara_norxmrs_nonce_bytes(), sarkara_norxmrs_tag_bytes());
+    test_sealedbox();
+
+    puts("ok");
+    return 0;
+}
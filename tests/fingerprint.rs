@@ -0,0 +1,86 @@
+extern crate rand;
+extern crate sarkara;
+
+use rand::{ FromEntropy, ChaChaRng };
+use sarkara::Packing;
+use sarkara::armor::Armored;
+use sarkara::fingerprint::Fingerprint;
+use sarkara::kex::KeyExchange;
+use sarkara::sign::Signature;
+use sarkara::sealedbox::SealedBox;
+use sarkara::kex::kyber::Kyber;
+use sarkara::sign::dilithium::Dilithium;
+use sarkara::aead::norx6441::Norx6441;
+
+
+fn test_fingerprint<T: Armored>(a: &T, b: &T) {
+    let fa = Fingerprint::of(a);
+    let fb = Fingerprint::of(b);
+    let fa2 = Fingerprint::of(&a.read_bytes(T::from_bytes));
+
+    assert_eq!(fa, fa2);
+    assert_ne!(fa, fb);
+
+    let hex = fa.to_hex();
+    assert_eq!(hex.len(), 64);
+    assert_eq!(hex, fa2.to_string());
+    assert!(hex.chars().all(|c| c.is_ascii_hexdigit()));
+
+    let words = fa.to_words();
+    assert_eq!(words.split(' ').count(), 32);
+    assert_eq!(words, fa2.to_words());
+    assert_ne!(words, fb.to_words());
+}
+
+
+#[test]
+fn test_fingerprint_keys() {
+    let mut rng = ChaChaRng::from_entropy();
+
+    let (_, pka) = Kyber::keypair(&mut rng);
+    let (_, pkb) = Kyber::keypair(&mut rng);
+    test_fingerprint(&pka, &pkb);
+
+    let (_, pka) = Dilithium::keypair(&mut rng);
+    let (_, pkb) = Dilithium::keypair(&mut rng);
+    test_fingerprint(&pka, &pkb);
+}
+
+#[test]
+fn test_fingerprint_algorithm_tagged() {
+    let mut rng = ChaChaRng::from_entropy();
+    let (sk, pk) = Kyber::keypair(&mut rng);
+
+    // same bytes, different type
+    let fa = Fingerprint::of(&pk);
+    let fb = Fingerprint::of(&pk.read_bytes(|pk| {
+        let mut buf = sk.read_bytes(|sk| sk.to_vec());
+        buf[..pk.len()].copy_from_slice(pk);
+        <Kyber as KeyExchange>::PrivateKey::from_bytes(&buf)
+    }));
+    assert_ne!(fa, fb);
+}
+
+#[test]
+fn test_sas() {
+    let mut rng = ChaChaRng::from_entropy();
+    let (bob_priv, bob_pub) = Kyber::keypair(&mut rng);
+    let (eve_priv, eve_pub) = Kyber::keypair(&mut rng);
+
+    let (alice_msg, alice_enc) = SealedBox::<Kyber, Norx6441>::send(&mut rng, &bob_pub);
+    let bob_dec = SealedBox::<Kyber, Norx6441>::recv(&bob_priv, &alice_msg);
+
+    assert_eq!(alice_enc.sas(), bob_dec.sas());
+    assert_eq!(alice_enc.sas().to_digits().len(), 6);
+    assert_eq!(alice_enc.sas().to_words(), bob_dec.sas().to_words());
+
+    // eve replaces bob public key
+    let (eve_msg, alice_enc) = SealedBox::<Kyber, Norx6441>::send(&mut rng, &eve_pub);
+    let eve_dec = SealedBox::<Kyber, Norx6441>::recv(&eve_priv, &eve_msg);
+    let (bob_msg, eve_enc) = SealedBox::<Kyber, Norx6441>::send(&mut rng, &bob_pub);
+    let bob_dec = SealedBox::<Kyber, Norx6441>::recv(&bob_priv, &bob_msg);
+
+    assert_eq!(alice_enc.sas(), eve_dec.sas());
+    assert_eq!(eve_enc.sas(), bob_dec.sas());
+    assert_ne!(alice_enc.sas(), bob_dec.sas());
+}
@@ -0,0 +1,145 @@
+extern crate rand;
+extern crate sarkara;
+
+use rand::{ Rng, RngCore, FromEntropy, ChaChaRng };
+use sarkara::{ Packing, Error };
+use sarkara::aead::AeadCipher;
+use sarkara::kex::{ CheckedExchange };
+use sarkara::handshake::{ HandshakeState, Transport, Pattern };
+
+use sarkara::kex::kyber::Kyber;
+use sarkara::aead::norx6441::Norx6441;
+
+
+fn run<KEX: CheckedExchange, AE: AeadCipher>(
+    mut initiator: HandshakeState<KEX, AE>,
+    mut responder: HandshakeState<KEX, AE>
+) -> (Transport<AE>, Transport<AE>) {
+    let mut rng = ChaChaRng::from_entropy();
+    let (mut a, mut b) = (&mut initiator, &mut responder);
+
+    while !a.is_finished() {
+        assert!(a.is_my_turn() && !b.is_my_turn());
+
+        let mut payload = vec![0; rng.gen_range(0, 65)];
+        rng.fill_bytes(&mut payload);
+        let message = a.write_message(&mut rng, &payload).unwrap();
+        assert_eq!(b.read_message(&message).unwrap(), payload);
+
+        std::mem::swap(&mut a, &mut b);
+    }
+
+    assert!(responder.is_finished());
+    assert_eq!(initiator.handshake_hash(), responder.handshake_hash());
+    (initiator.into_transport().unwrap(), responder.into_transport().unwrap())
+}
+
+fn test_transport<AE: AeadCipher>(mut a: Transport<AE>, mut b: Transport<AE>) {
+    let mut rng = ChaChaRng::from_entropy();
+    assert_eq!(a.handshake_hash(), b.handshake_hash());
+
+    for _ in 0..3 {
+        roundtrip(&mut rng, &mut a, &mut b);
+        roundtrip(&mut rng, &mut b, &mut a);
+    }
+
+    // replay
+    let mut ct = vec![0; AE::TAG_LENGTH];
+    let mut ot = vec![0; 0];
+    a.seal(&[], &mut ct).unwrap();
+    b.open(&ct, &mut ot).unwrap();
+    assert_eq!(b.open(&ct, &mut ot), Err(Error::VerificationFailed));
+}
+
+fn roundtrip<AE: AeadCipher>(rng: &mut ChaChaRng, x: &mut Transport<AE>, y: &mut Transport<AE>) {
+    let mut pt = vec![0; rng.gen_range(0, 256)];
+    let mut ct = vec![0; pt.len() + AE::TAG_LENGTH];
+    let mut ot = vec![0; pt.len()];
+    rng.fill_bytes(&mut pt);
+
+    x.seal(&pt, &mut ct).unwrap();
+    y.open(&ct, &mut ot).unwrap();
+    assert_eq!(pt, ot);
+}
+
+fn test_ik<KEX: CheckedExchange, AE: AeadCipher>() {
+    let mut rng = ChaChaRng::from_entropy();
+    let (isk, ipk) = KEX::keypair(&mut rng);
+    let (rsk, rpk) = KEX::keypair(&mut rng);
+    let rpk2 = rpk.read_bytes(KEX::PublicKey::from_bytes);
+    let ipk2 = ipk.read_bytes(KEX::PublicKey::from_bytes);
+
+    let initiator = HandshakeState::<KEX, AE>::initiator(Pattern::IK, (isk, ipk), Some(rpk2), b"prologue").unwrap();
+    let responder = HandshakeState::<KEX, AE>::responder(Pattern::IK, (rsk, rpk), b"prologue");
+    assert!(responder.remote_static().is_none());
+
+    let mut initiator = initiator;
+    let mut responder = responder;
+    let message = initiator.write_message(&mut rng, b"hello").unwrap();
+    assert_eq!(responder.read_message(&message).unwrap(), b"hello");
+    assert!(responder.remote_static().unwrap().read_bytes(|x| ipk2.read_bytes(|y| x == y)));
+    let message = responder.write_message(&mut rng, b"world").unwrap();
+    assert_eq!(initiator.read_message(&message).unwrap(), b"world");
+
+    let (a, b) = (initiator.into_transport().unwrap(), responder.into_transport().unwrap());
+    test_transport(a, b);
+}
+
+fn test_xx<KEX: CheckedExchange, AE: AeadCipher>() {
+    let mut rng = ChaChaRng::from_entropy();
+    let initiator = HandshakeState::<KEX, AE>::initiator(Pattern::XX, KEX::keypair(&mut rng), None, &[]).unwrap();
+    let responder = HandshakeState::<KEX, AE>::responder(Pattern::XX, KEX::keypair(&mut rng), &[]);
+
+    let (a, b) = run(initiator, responder);
+    test_transport(a, b);
+}
+
+fn test_failure<KEX: CheckedExchange, AE: AeadCipher>() {
+    let mut rng = ChaChaRng::from_entropy();
+    let (rsk, rpk) = KEX::keypair(&mut rng);
+    let (_, wrong) = KEX::keypair(&mut rng);
+
+    // IK needs the responder static key
+    assert!(HandshakeState::<KEX, AE>::initiator(Pattern::IK, KEX::keypair(&mut rng), None, &[]).is_err());
+
+    // wrong responder static key
+    let mut initiator = HandshakeState::<KEX, AE>::initiator(Pattern::IK, KEX::keypair(&mut rng), Some(wrong), &[]).unwrap();
+    let mut responder = HandshakeState::<KEX, AE>::responder(Pattern::IK, (rsk, rpk), &[]);
+    let message = initiator.write_message(&mut rng, &[]).unwrap();
+    assert!(responder.read_message(&message).is_err());
+    assert_eq!(responder.write_message(&mut rng, &[]), Err(Error::InvalidState));
+
+    // prologue mismatch, tampering, truncation, order
+    let new = |rng: &mut ChaChaRng, prologue: &[u8]| (
+        HandshakeState::<KEX, AE>::initiator(Pattern::XX, KEX::keypair(&mut *rng), None, &[]).unwrap(),
+        HandshakeState::<KEX, AE>::responder(Pattern::XX, KEX::keypair(&mut *rng), prologue)
+    );
+
+    let (mut initiator, mut responder) = new(&mut rng, b"other");
+    let message = initiator.write_message(&mut rng, &[]).unwrap();
+    responder.read_message(&message).unwrap();
+    let message = responder.write_message(&mut rng, &[]).unwrap();
+    assert_eq!(initiator.read_message(&message), Err(Error::VerificationFailed));
+
+    let (mut initiator, mut responder) = new(&mut rng, &[]);
+    let message = initiator.write_message(&mut rng, &[]).unwrap();
+    responder.read_message(&message).unwrap();
+    let mut message = responder.write_message(&mut rng, &[]).unwrap();
+    let last = message.len() - 1;
+    message[last] ^= 0x42;
+    assert_eq!(initiator.read_message(&message), Err(Error::VerificationFailed));
+    assert!(initiator.into_transport().is_err());
+
+    let (mut initiator, mut responder) = new(&mut rng, &[]);
+    assert_eq!(responder.write_message(&mut rng, &[]), Err(Error::InvalidState));
+    let message = initiator.write_message(&mut rng, &[]).unwrap();
+    assert!(responder.read_message(&message[..message.len() - 1]).is_err());
+}
+
+
+#[test]
+fn test_kyber_norx() {
+    test_ik::<Kyber, Norx6441>();
+    test_xx::<Kyber, Norx6441>();
+    test_failure::<Kyber, Norx6441>();
+}
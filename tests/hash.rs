@@ -0,0 +1,120 @@
+extern crate rand;
+extern crate sarkara;
+
+use std::io::Write;
+use rand::{ Rng, RngCore, FromEntropy, ChaChaRng };
+use sarkara::hash::{ Hash, Xof, XofReader };
+use sarkara::hash::norx::NorxHash;
+
+
+fn hex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn test_hash<H: Hash + Write>() {
+    let mut rng = ChaChaRng::from_entropy();
+
+    for i in 0..512 {
+        let mut data = vec![0u8; i];
+        rng.fill_bytes(&mut data);
+        let split = rng.gen_range(0, i + 1);
+
+        let mut a = vec![0u8; H::OUTPUT_LENGTH];
+        let mut b = vec![0u8; H::OUTPUT_LENGTH];
+        let mut c = vec![0u8; H::OUTPUT_LENGTH];
+
+        H::digest(&data, &mut a);
+
+        let mut hasher = H::new();
+        hasher.update(&data[..split]);
+        hasher.update(&data[split..]);
+        hasher.finalize(&mut b);
+        assert_eq!(a, b);
+
+        let mut hasher = H::new();
+        hasher.write_all(&data).unwrap();
+        hasher.finalize(&mut c);
+        assert_eq!(a, c);
+
+        data.push(0);
+        H::digest(&data, &mut c);
+        assert_ne!(a, c);
+    }
+}
+
+fn test_xof<H: Xof>() {
+    let mut rng = ChaChaRng::from_entropy();
+    let mut data = vec![0u8; rng.gen_range(0, 512)];
+    rng.fill_bytes(&mut data);
+
+    let mut a = vec![0u8; 1024];
+    let mut b = vec![0u8; 1024];
+
+    let mut hasher = H::new();
+    hasher.update(&data);
+    hasher.xof().read(&mut a);
+
+    let mut hasher = H::new();
+    hasher.update(&data);
+    let mut reader = hasher.xof();
+    for chunk in b.chunks_mut(rng.gen_range(1, 200)) {
+        reader.read(chunk);
+    }
+    assert_eq!(a, b);
+
+    let mut c = vec![0u8; H::OUTPUT_LENGTH];
+    H::digest(&data, &mut c);
+    assert_eq!(&a[..H::OUTPUT_LENGTH], &c[..]);
+}
+
+
+#[test]
+fn test_norx_hash() {
+    test_hash::<NorxHash>();
+    test_xof::<NorxHash>();
+}
+
+#[test]
+fn test_norx_hash_vectors() {
+    let vectors: &[(&[u8], &str, &str)] = &[
+        (
+            b"",
+            "372f7a5cc7b004d7f353cad5db7fae023a26f88d8905f16bd453ea14e6d9b1f8",
+            "372f7a5cc7b004d7f353cad5db7fae023a26f88d8905f16bd453ea14e6d9b1f8\
+             4bed2eb20305aa44f2329ea77db621577b0068faa5cf02952801d809c65d23ba\
+             29c752ba3a0cec5639ebe56f2664ae0a7e0a8ff9c6ac58fa5124737b4423914a\
+             125fa1ca17a3e1a4ded06c707ec76e32a79a4c65f058849815454909e9fed674"
+        ),
+        (
+            b"abc",
+            "770c1a7be2b011b01fa96dbcb09831d8f59c529ca4f4c8578383994645b1f06c",
+            "770c1a7be2b011b01fa96dbcb09831d8f59c529ca4f4c8578383994645b1f06c\
+             c3fe02320b29f67f0af9c3ba31367fcb8cb4ab8bf888a3c7203a94cdacb6890b\
+             c40c4834c4128ee9b81f7618d9f4582d35990f53c3488a1ab99b5f8d3e136541\
+             8ee5959458554daa98bee13982cbb3fca7ddcee96a51ec4565534c74814459ad"
+        ),
+        (
+            &[0x61; 200],
+            "6326539581e688b891ef8c2045520234c5cce50a87c262d5d2cb471d6fd1ee97",
+            "6326539581e688b891ef8c2045520234c5cce50a87c262d5d2cb471d6fd1ee97\
+             5e58661c9938027c0fb04b376f4ff817c4a4026f55b50a8e6cc95e2feea40f3a\
+             9ff703333bde1c787ae646622d51cc65bc0999d16ee7b8d0d55026fe3238e020\
+             dc2c1b9637887f165f8b04cc36faff5f668537e7e03feb95a74184d94906893a"
+        )
+    ];
+
+    for &(data, digest, xof) in vectors {
+        let mut output = [0; 32];
+        NorxHash::digest(data, &mut output);
+        assert_eq!(&output[..], &hex(digest)[..]);
+
+        let mut output = [0; 128];
+        let mut hasher = NorxHash::new();
+        hasher.update(data);
+        hasher.xof().read(&mut output);
+        assert_eq!(&output[..], &hex(xof)[..]);
+    }
+}
@@ -0,0 +1,428 @@
+extern crate rand;
+extern crate sarkara;
+
+use rand::{ Rng, RngCore, SeedableRng, FromEntropy, ChaChaRng };
+use sarkara::{ Packing, Error, Length, SEED_LENGTH };
+use sarkara::aead::AeadCipher;
+use sarkara::kex::{ KeyExchange, CheckedExchange, SeedableExchange };
+use sarkara::hpke::{ Hpke, Context, Mode };
+use sarkara::rand::norx::NorxRng;
+use sarkara::registry::Identified;
+
+use sarkara::kex::kyber::Kyber;
+use sarkara::aead::norx6441::Norx6441;
+use sarkara::aead::norx_mrs::NorxMRS;
+
+
+fn hex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn roundtrip<AE: AeadCipher>(sender: &mut Context<AE>, receiver: &mut Context<AE>) {
+    let mut rng = ChaChaRng::from_entropy();
+
+    for _ in 0..3 {
+        let mut aad = vec![0u8; rng.gen_range(0, 34)];
+        let mut pt = vec![0u8; rng.gen_range(0, 256)];
+        let mut ct = vec![0u8; pt.len() + AE::TAG_LENGTH];
+        let mut ot = vec![0u8; pt.len()];
+        rng.fill_bytes(&mut aad);
+        rng.fill_bytes(&mut pt);
+
+        sender.seal(&aad, &pt, &mut ct).unwrap();
+        receiver.open(&aad, &ct, &mut ot).unwrap();
+        assert_eq!(pt, ot);
+    }
+
+    assert_eq!(sender.export(b"context", 64).unwrap(), receiver.export(b"context", 64).unwrap());
+    assert_ne!(sender.export(b"context", 32).unwrap(), sender.export(b"other", 32).unwrap());
+}
+
+fn test_hpke<KEX, AE>()
+    where
+        KEX: KeyExchange + Identified,
+        AE: AeadCipher + Identified
+{
+    let mut rng = ChaChaRng::from_entropy();
+    let (skb, pkb) = KEX::keypair(&mut rng);
+    let psk = Some((&[0x42; 32][..], &b"psk id"[..]));
+
+    let (m, mut sender) = Hpke::<KEX, AE>::setup_sender(&mut rng, &pkb, b"info", None).unwrap();
+    let mut receiver = Hpke::<KEX, AE>::setup_receiver(&skb, &m, b"info", None).unwrap();
+    roundtrip(&mut sender, &mut receiver);
+
+    let (m, mut sender) = Hpke::<KEX, AE>::setup_sender(&mut rng, &pkb, b"info", psk).unwrap();
+    let mut receiver = Hpke::<KEX, AE>::setup_receiver(&skb, &m, b"info", psk).unwrap();
+    roundtrip(&mut sender, &mut receiver);
+}
+
+fn test_mismatch<KEX, AE>()
+    where
+        KEX: KeyExchange + Identified,
+        AE: AeadCipher + Identified
+{
+    let mut rng = ChaChaRng::from_entropy();
+    let (skb, pkb) = KEX::keypair(&mut rng);
+    let psk = Some((&[0x42; 32][..], &b"psk id"[..]));
+    let psk2 = Some((&[0x24; 32][..], &b"psk id"[..]));
+
+    let (m, sender) = Hpke::<KEX, AE>::setup_sender(&mut rng, &pkb, b"info", psk).unwrap();
+    let expected = sender.export(&[], 32).unwrap();
+
+    let receivers = vec![
+        Hpke::<KEX, AE>::setup_receiver(&skb, &m, b"other", psk).unwrap(),
+        Hpke::<KEX, AE>::setup_receiver(&skb, &m, b"info", psk2).unwrap(),
+        Hpke::<KEX, AE>::setup_receiver(&skb, &m, b"info", None).unwrap()
+    ];
+    for receiver in receivers {
+        assert_ne!(receiver.export(&[], 32).unwrap(), expected);
+    }
+}
+
+fn test_order<KEX, AE>()
+    where
+        KEX: KeyExchange + Identified,
+        AE: AeadCipher + Identified
+{
+    let mut rng = ChaChaRng::from_entropy();
+    let (skb, pkb) = KEX::keypair(&mut rng);
+
+    let (m, mut sender) = Hpke::<KEX, AE>::setup_sender(&mut rng, &pkb, &[], None).unwrap();
+    let mut receiver = Hpke::<KEX, AE>::setup_receiver(&skb, &m, &[], None).unwrap();
+
+    let mut pt = vec![0u8; 32];
+    let mut ct1 = vec![0u8; pt.len() + AE::TAG_LENGTH];
+    let mut ct2 = vec![0u8; pt.len() + AE::TAG_LENGTH];
+    let mut ot = vec![0u8; pt.len()];
+    rng.fill_bytes(&mut pt);
+    sender.seal(&[], &pt, &mut ct1).unwrap();
+    sender.seal(&[], &pt, &mut ct2).unwrap();
+    assert_ne!(ct1, ct2);
+
+    assert!(receiver.open(&[], &ct2, &mut ot).is_err());
+    receiver.open(&[], &ct1, &mut ot).unwrap();
+    assert_eq!(pt, ot);
+}
+
+fn test_psk_inputs<KEX, AE>()
+    where
+        KEX: KeyExchange + Identified,
+        AE: AeadCipher + Identified
+{
+    let secret = [0; 32];
+    let psk = [0x42; 32];
+
+    assert!(matches!(
+        Hpke::<KEX, AE>::key_schedule(Mode::Psk, &secret, &[], Some((&psk[..31], b"id"))),
+        Err(Error::Length(Length::Key))
+    ));
+    assert!(matches!(
+        Hpke::<KEX, AE>::key_schedule(Mode::Psk, &secret, &[], Some((&psk, &[]))),
+        Err(Error::Length(Length::Key))
+    ));
+    assert!(matches!(
+        Hpke::<KEX, AE>::key_schedule(Mode::Base, &secret, &[], Some((&psk, b"id"))),
+        Err(Error::InvalidState)
+    ));
+    assert!(matches!(
+        Hpke::<KEX, AE>::key_schedule(Mode::Psk, &secret, &[], None),
+        Err(Error::InvalidState)
+    ));
+
+    let ctx = Hpke::<KEX, AE>::key_schedule(Mode::Base, &secret, &[], None).unwrap();
+    assert!(matches!(ctx.export(&[], 1 << 16), Err(Error::Length(Length::Output))));
+}
+
+struct Vector {
+    mode: Mode,
+    enc: &'static str,
+    shared_secret: &'static str,
+    key: &'static str,
+    base_nonce: &'static str,
+    ct0: &'static str,
+    ct1: &'static str,
+    exported: &'static str
+}
+
+const KAT_KEYPAIR_SEED: [u8; SEED_LENGTH] = [0x01; SEED_LENGTH];
+const KAT_RNG_SEED: [u8; 32] = [0x02; 32];
+const KAT_INFO: &[u8] = b"sarkara kat info";
+const KAT_PSK: (&[u8], &[u8]) = (&[0x42; 32], b"sarkara kat psk");
+const KAT_PT: &[u8] = b"sarkara kat plaintext";
+const KAT_AAD: [&[u8]; 2] = [b"Count-0", b"Count-1"];
+const KAT_CONTEXT: &[u8] = b"sarkara kat context";
+
+const KAT_KYBER_NORX: [Vector; 2] = [
+    Vector {
+        mode: Mode::Base,
+        enc: "9b3d1a36855e4f901eeb0c6c5bc4fad67c3889365c63e1a6d69bd9228bf0b867\
+              195c01aeb83a7f27925fdbde5ecf5fa2808d6f2d0ca3959332b860704d47ab1b\
+              242b7fb72a65f088f0baab9579d3478abcd913b165ca0bbf2f2b58dcc48bb181\
+              88b4cc452acbdf1df8dfa2d60f5fedb2430474b446e6052481b7509115491ca6\
+              1978e9fd2b32ae3af7df6979dca2906e95a86c16f2b379e4460ef79102e1f08c\
+              c6829fc7a6043ddec4766646a2bcb7af04db4a116cc1542d6381b987b3fe69be\
+              cf6a3c2d195f4bbd07213eff0e109e5da2a4814484b254378d046ceeada2d68c\
+              a6b455a6aa25e9090e6042332cef3e1dd0e3d0865c58c52f494653d86e4f189b\
+              880046dafbf14ebae010409d075cc469c9141bfddae0e277d92b16642ff92f81\
+              b06af1b87958a225d68b3ed98d892d447e8a9f72ad0bc7d415f305e21a9a5ee3\
+              d318620b84b258d795601a1ea40943d32c7fe42606b07c7054e557c83386184c\
+              f0b24bdcc463201ddef2bbe33cb6be871474a8daf6f14567bcc00549f776c4d0\
+              c5b63b1b8428ea79a5454bf56a2821eaebec233b52f5d9dfdbacf143bca82c48\
+              f93b307fe98ddece1c697c7c3e6160117e62ebc1c19500b330dfd673702ec51f\
+              830052149adae49a307f559249bb4606b03765142f510871fc83f0a73fcad83f\
+              34c66a875bd1d5958af642215c8182f437ebb97ae0e7be668992e622621470e9\
+              3a431007df7c12673f3806dd21a661a179e659f301fd23d5f3ef37aa824e0370\
+              602cc18f5d9b7c374229d675dc88a740cf99d7d917d9a183ca36a37da1f303f2\
+              b4720cf61e6fe92fcc24949c343a8f2dff24502e955fbffff68ac7f3081d8006\
+              c68f17a1c835fc9f67b0b0003a7eb7c6c3002907a4e0c1f3d10799dedd1a8301\
+              9655d7d394a928fca2cfa19b06c90b1273cc3561db3331d095cc803b4aee84d7\
+              ff125b4e1ec43dd9c6bc69c719448442a2841f505f314ff0f6972b9628fe1437\
+              56c1721295f2ef26f8d4392399efd750ead3c301778b1c006c3ec7a152fe34a9\
+              b2eef78760ccdf09ebf88ca4d7845589d0ca5e0ff85531b5980452e3a585a056\
+              2f2e5603af8786f85d8619fbd9c85ed35f530b0514964261bbf8f1f8894581af\
+              5845fd86f6ef58c8f998baf232aebdeb16b4603012f78d60977b2076f6883eae\
+              dfdf0d2ae847590d690da14e7e0e8a73effbd01b8dff92adb16b8c9dd2f7d220\
+              b2c4ec227e23defd32b53e7383a07abc1ebc6238cb18d839e40f3e4918af7408\
+              0cb3554a9eecea600e47dd3ba0e5613343556c3f283c7412a3cace6a3502db51\
+              ee895ef2fd72857f40d00df79eb44e6bbc8eaf800f06f64da55820626b1fdc1b\
+              8a7e45a61c3be0fbe8629f363b1391b6f3efd948e5909283605c2d7740ea8163\
+              42b6742d5cb141b4c8a959fac1cdc1459ee51227c86d2e89ec622d11878d0525\
+              a6667539729ebfbe322b00d9ac4646890da9939355aff1ad207eb268ace7137c\
+              17593949f5486382cc064887c6c3688580689ea742e80b065da6f797d5ab6ed9\
+              0f5995e22444a80e9316f0df94e54d1f8c8ce59cc0ed0fb67c40890721332ba6\
+              7a198e331e0833103a90014b9f3543240bc1add64a337471dd496bff797a2c15",
+        shared_secret: "5b16a376c6d350f0077b59848760d272c57671c4f4f08aa6d2346a7c9526112c",
+        key: "047d7aa0fbcdf68c307d9c724894456718f7a6c0b5e2ad17afda51c9ccda0e97",
+        base_nonce: "e7dd17534aa01cdabc39eb8701e7aa0e7c6ae95e7ae774535a90833bead17c03",
+        ct0: "f6ba99bb34b59ab2cf39ea8fe8cd4287e34928d7c258152bd7b10ebdaa9ae308\
+              e7abf5602126b6615be8eb7ff8a1f422c9eb217cd0",
+        ct1: "923bb65148a4648ac9d97cefca8e810d4b273e4387120241d16efb0b41f8efdc\
+              841ef578f178fc6e56dd3f19d7a69266c6c684e6c4",
+        exported: "08b57485dcfda22cf435c7d7ae930da9de5626868007c4c45442d28b590c6434"
+    },
+    Vector {
+        mode: Mode::Psk,
+        enc: "9b3d1a36855e4f901eeb0c6c5bc4fad67c3889365c63e1a6d69bd9228bf0b867\
+              195c01aeb83a7f27925fdbde5ecf5fa2808d6f2d0ca3959332b860704d47ab1b\
+              242b7fb72a65f088f0baab9579d3478abcd913b165ca0bbf2f2b58dcc48bb181\
+              88b4cc452acbdf1df8dfa2d60f5fedb2430474b446e6052481b7509115491ca6\
+              1978e9fd2b32ae3af7df6979dca2906e95a86c16f2b379e4460ef79102e1f08c\
+              c6829fc7a6043ddec4766646a2bcb7af04db4a116cc1542d6381b987b3fe69be\
+              cf6a3c2d195f4bbd07213eff0e109e5da2a4814484b254378d046ceeada2d68c\
+              a6b455a6aa25e9090e6042332cef3e1dd0e3d0865c58c52f494653d86e4f189b\
+              880046dafbf14ebae010409d075cc469c9141bfddae0e277d92b16642ff92f81\
+              b06af1b87958a225d68b3ed98d892d447e8a9f72ad0bc7d415f305e21a9a5ee3\
+              d318620b84b258d795601a1ea40943d32c7fe42606b07c7054e557c83386184c\
+              f0b24bdcc463201ddef2bbe33cb6be871474a8daf6f14567bcc00549f776c4d0\
+              c5b63b1b8428ea79a5454bf56a2821eaebec233b52f5d9dfdbacf143bca82c48\
+              f93b307fe98ddece1c697c7c3e6160117e62ebc1c19500b330dfd673702ec51f\
+              830052149adae49a307f559249bb4606b03765142f510871fc83f0a73fcad83f\
+              34c66a875bd1d5958af642215c8182f437ebb97ae0e7be668992e622621470e9\
+              3a431007df7c12673f3806dd21a661a179e659f301fd23d5f3ef37aa824e0370\
+              602cc18f5d9b7c374229d675dc88a740cf99d7d917d9a183ca36a37da1f303f2\
+              b4720cf61e6fe92fcc24949c343a8f2dff24502e955fbffff68ac7f3081d8006\
+              c68f17a1c835fc9f67b0b0003a7eb7c6c3002907a4e0c1f3d10799dedd1a8301\
+              9655d7d394a928fca2cfa19b06c90b1273cc3561db3331d095cc803b4aee84d7\
+              ff125b4e1ec43dd9c6bc69c719448442a2841f505f314ff0f6972b9628fe1437\
+              56c1721295f2ef26f8d4392399efd750ead3c301778b1c006c3ec7a152fe34a9\
+              b2eef78760ccdf09ebf88ca4d7845589d0ca5e0ff85531b5980452e3a585a056\
+              2f2e5603af8786f85d8619fbd9c85ed35f530b0514964261bbf8f1f8894581af\
+              5845fd86f6ef58c8f998baf232aebdeb16b4603012f78d60977b2076f6883eae\
+              dfdf0d2ae847590d690da14e7e0e8a73effbd01b8dff92adb16b8c9dd2f7d220\
+              b2c4ec227e23defd32b53e7383a07abc1ebc6238cb18d839e40f3e4918af7408\
+              0cb3554a9eecea600e47dd3ba0e5613343556c3f283c7412a3cace6a3502db51\
+              ee895ef2fd72857f40d00df79eb44e6bbc8eaf800f06f64da55820626b1fdc1b\
+              8a7e45a61c3be0fbe8629f363b1391b6f3efd948e5909283605c2d7740ea8163\
+              42b6742d5cb141b4c8a959fac1cdc1459ee51227c86d2e89ec622d11878d0525\
+              a6667539729ebfbe322b00d9ac4646890da9939355aff1ad207eb268ace7137c\
+              17593949f5486382cc064887c6c3688580689ea742e80b065da6f797d5ab6ed9\
+              0f5995e22444a80e9316f0df94e54d1f8c8ce59cc0ed0fb67c40890721332ba6\
+              7a198e331e0833103a90014b9f3543240bc1add64a337471dd496bff797a2c15",
+        shared_secret: "5b16a376c6d350f0077b59848760d272c57671c4f4f08aa6d2346a7c9526112c",
+        key: "19e19e17f240a184b985421e5e81ccd9569e127c361f4f22f09fe00a023ff575",
+        base_nonce: "305f4344c24823b701a1a07dd6de49ed39bd7ca864c2e55e0448b1b1b12542d2",
+        ct0: "cc1001122da488df3f7971e89634cd7884a6d5a1b00e82e4b2c74f63a99ed5d3\
+              cd2e2e898b2236c3bf8c56677915a8f769271e7458",
+        ct1: "87062471cf33a2f0684aac1f4ed900f60eac26587c20df604e02132881fbdb5a\
+              8d5bc3cd4bc5530e1061112ef8c6d8105b4a88f061",
+        exported: "a78bd58ea1a483e6ad1a39ce14665d50f6f2f670decd5b16e9a339143a53f617"
+    }
+];
+
+const KAT_KYBER_NORXMRS: [Vector; 2] = [
+    Vector {
+        mode: Mode::Base,
+        enc: "9b3d1a36855e4f901eeb0c6c5bc4fad67c3889365c63e1a6d69bd9228bf0b867\
+              195c01aeb83a7f27925fdbde5ecf5fa2808d6f2d0ca3959332b860704d47ab1b\
+              242b7fb72a65f088f0baab9579d3478abcd913b165ca0bbf2f2b58dcc48bb181\
+              88b4cc452acbdf1df8dfa2d60f5fedb2430474b446e6052481b7509115491ca6\
+              1978e9fd2b32ae3af7df6979dca2906e95a86c16f2b379e4460ef79102e1f08c\
+              c6829fc7a6043ddec4766646a2bcb7af04db4a116cc1542d6381b987b3fe69be\
+              cf6a3c2d195f4bbd07213eff0e109e5da2a4814484b254378d046ceeada2d68c\
+              a6b455a6aa25e9090e6042332cef3e1dd0e3d0865c58c52f494653d86e4f189b\
+              880046dafbf14ebae010409d075cc469c9141bfddae0e277d92b16642ff92f81\
+              b06af1b87958a225d68b3ed98d892d447e8a9f72ad0bc7d415f305e21a9a5ee3\
+              d318620b84b258d795601a1ea40943d32c7fe42606b07c7054e557c83386184c\
+              f0b24bdcc463201ddef2bbe33cb6be871474a8daf6f14567bcc00549f776c4d0\
+              c5b63b1b8428ea79a5454bf56a2821eaebec233b52f5d9dfdbacf143bca82c48\
+              f93b307fe98ddece1c697c7c3e6160117e62ebc1c19500b330dfd673702ec51f\
+              830052149adae49a307f559249bb4606b03765142f510871fc83f0a73fcad83f\
+              34c66a875bd1d5958af642215c8182f437ebb97ae0e7be668992e622621470e9\
+              3a431007df7c12673f3806dd21a661a179e659f301fd23d5f3ef37aa824e0370\
+              602cc18f5d9b7c374229d675dc88a740cf99d7d917d9a183ca36a37da1f303f2\
+              b4720cf61e6fe92fcc24949c343a8f2dff24502e955fbffff68ac7f3081d8006\
+              c68f17a1c835fc9f67b0b0003a7eb7c6c3002907a4e0c1f3d10799dedd1a8301\
+              9655d7d394a928fca2cfa19b06c90b1273cc3561db3331d095cc803b4aee84d7\
+              ff125b4e1ec43dd9c6bc69c719448442a2841f505f314ff0f6972b9628fe1437\
+              56c1721295f2ef26f8d4392399efd750ead3c301778b1c006c3ec7a152fe34a9\
+              b2eef78760ccdf09ebf88ca4d7845589d0ca5e0ff85531b5980452e3a585a056\
+              2f2e5603af8786f85d8619fbd9c85ed35f530b0514964261bbf8f1f8894581af\
+              5845fd86f6ef58c8f998baf232aebdeb16b4603012f78d60977b2076f6883eae\
+              dfdf0d2ae847590d690da14e7e0e8a73effbd01b8dff92adb16b8c9dd2f7d220\
+              b2c4ec227e23defd32b53e7383a07abc1ebc6238cb18d839e40f3e4918af7408\
+              0cb3554a9eecea600e47dd3ba0e5613343556c3f283c7412a3cace6a3502db51\
+              ee895ef2fd72857f40d00df79eb44e6bbc8eaf800f06f64da55820626b1fdc1b\
+              8a7e45a61c3be0fbe8629f363b1391b6f3efd948e5909283605c2d7740ea8163\
+              42b6742d5cb141b4c8a959fac1cdc1459ee51227c86d2e89ec622d11878d0525\
+              a6667539729ebfbe322b00d9ac4646890da9939355aff1ad207eb268ace7137c\
+              17593949f5486382cc064887c6c3688580689ea742e80b065da6f797d5ab6ed9\
+              0f5995e22444a80e9316f0df94e54d1f8c8ce59cc0ed0fb67c40890721332ba6\
+              7a198e331e0833103a90014b9f3543240bc1add64a337471dd496bff797a2c15",
+        shared_secret: "5b16a376c6d350f0077b59848760d272c57671c4f4f08aa6d2346a7c9526112c",
+        key: "59e5ad6d2bb2b7b9c483f9646b62b7df22c3a50d9e357f7c8641b8185c9e61a6",
+        base_nonce: "3f89a20ce17f13ff4c8eb74a75c01f54",
+        ct0: "b57173f8e4fc2b85051d89a094fefb2587cacf33b72f7dbdd3be9fa795e887e0\
+              61d780897f92bb485c5a6a2b5dc2e6fed8c86da46d",
+        ct1: "9fbd198e12cebf013d9d4bed1be822468efaf36ee8ea264bba3f04c674bd2678\
+              f46a4e18b4d6ad95a6fa37b69d96fbfc473c3d0761",
+        exported: "c24a56b4d0340aa17c50da013aec7842f96a0dbb79fc533f1a7421247309115e"
+    },
+    Vector {
+        mode: Mode::Psk,
+        enc: "9b3d1a36855e4f901eeb0c6c5bc4fad67c3889365c63e1a6d69bd9228bf0b867\
+              195c01aeb83a7f27925fdbde5ecf5fa2808d6f2d0ca3959332b860704d47ab1b\
+              242b7fb72a65f088f0baab9579d3478abcd913b165ca0bbf2f2b58dcc48bb181\
+              88b4cc452acbdf1df8dfa2d60f5fedb2430474b446e6052481b7509115491ca6\
+              1978e9fd2b32ae3af7df6979dca2906e95a86c16f2b379e4460ef79102e1f08c\
+              c6829fc7a6043ddec4766646a2bcb7af04db4a116cc1542d6381b987b3fe69be\
+              cf6a3c2d195f4bbd07213eff0e109e5da2a4814484b254378d046ceeada2d68c\
+              a6b455a6aa25e9090e6042332cef3e1dd0e3d0865c58c52f494653d86e4f189b\
+              880046dafbf14ebae010409d075cc469c9141bfddae0e277d92b16642ff92f81\
+              b06af1b87958a225d68b3ed98d892d447e8a9f72ad0bc7d415f305e21a9a5ee3\
+              d318620b84b258d795601a1ea40943d32c7fe42606b07c7054e557c83386184c\
+              f0b24bdcc463201ddef2bbe33cb6be871474a8daf6f14567bcc00549f776c4d0\
+              c5b63b1b8428ea79a5454bf56a2821eaebec233b52f5d9dfdbacf143bca82c48\
+              f93b307fe98ddece1c697c7c3e6160117e62ebc1c19500b330dfd673702ec51f\
+              830052149adae49a307f559249bb4606b03765142f510871fc83f0a73fcad83f\
+              34c66a875bd1d5958af642215c8182f437ebb97ae0e7be668992e622621470e9\
+              3a431007df7c12673f3806dd21a661a179e659f301fd23d5f3ef37aa824e0370\
+              602cc18f5d9b7c374229d675dc88a740cf99d7d917d9a183ca36a37da1f303f2\
+              b4720cf61e6fe92fcc24949c343a8f2dff24502e955fbffff68ac7f3081d8006\
+              c68f17a1c835fc9f67b0b0003a7eb7c6c3002907a4e0c1f3d10799dedd1a8301\
+              9655d7d394a928fca2cfa19b06c90b1273cc3561db3331d095cc803b4aee84d7\
+              ff125b4e1ec43dd9c6bc69c719448442a2841f505f314ff0f6972b9628fe1437\
+              56c1721295f2ef26f8d4392399efd750ead3c301778b1c006c3ec7a152fe34a9\
+              b2eef78760ccdf09ebf88ca4d7845589d0ca5e0ff85531b5980452e3a585a056\
+              2f2e5603af8786f85d8619fbd9c85ed35f530b0514964261bbf8f1f8894581af\
+              5845fd86f6ef58c8f998baf232aebdeb16b4603012f78d60977b2076f6883eae\
+              dfdf0d2ae847590d690da14e7e0e8a73effbd01b8dff92adb16b8c9dd2f7d220\
+              b2c4ec227e23defd32b53e7383a07abc1ebc6238cb18d839e40f3e4918af7408\
+              0cb3554a9eecea600e47dd3ba0e5613343556c3f283c7412a3cace6a3502db51\
+              ee895ef2fd72857f40d00df79eb44e6bbc8eaf800f06f64da55820626b1fdc1b\
+              8a7e45a61c3be0fbe8629f363b1391b6f3efd948e5909283605c2d7740ea8163\
+              42b6742d5cb141b4c8a959fac1cdc1459ee51227c86d2e89ec622d11878d0525\
+              a6667539729ebfbe322b00d9ac4646890da9939355aff1ad207eb268ace7137c\
+              17593949f5486382cc064887c6c3688580689ea742e80b065da6f797d5ab6ed9\
+              0f5995e22444a80e9316f0df94e54d1f8c8ce59cc0ed0fb67c40890721332ba6\
+              7a198e331e0833103a90014b9f3543240bc1add64a337471dd496bff797a2c15",
+        shared_secret: "5b16a376c6d350f0077b59848760d272c57671c4f4f08aa6d2346a7c9526112c",
+        key: "21b41cfaa06ee1044a140f021303e422ba1be464752afe59fdd66111a2fbc537",
+        base_nonce: "f03af2a285f5a5e32fb10291d35058bb",
+        ct0: "120d5f883614153c3728c93bcacfdbe2e1192b0ccd88565cd038eef7f63bc7bd\
+              de72cc4904ff8df7ade4d6596cb0d85385f5c5ba37",
+        ct1: "256a4e8463d251ff4eb1528c5b7b1c8791dfccd8f57043e4546bbf0b8bf6359e\
+              66f5c0b016bfbbc8d3e101a984b9275d08bd043e36",
+        exported: "8891c519126f8b820328718830b9160e622dd9e08e094cd37f8a6e98a119420f"
+    }
+];
+
+/// The vectors run the whole setup: the recipient keypair is
+/// `keypair_from_seed(KAT_KEYPAIR_SEED)`, the sender rng is `NorxRng::from_seed(KAT_RNG_SEED)`,
+/// `ct0` and `ct1` seal `KAT_PT` with `KAT_AAD` at sequence 0 and 1,
+/// and `exported` is `export(KAT_CONTEXT, 32)`.
+/// `shared_secret`, `key` and `base_nonce` are the key schedule values of the same run.
+fn test_kat<KEX, AE>(vectors: &[Vector])
+    where
+        KEX: SeedableExchange + CheckedExchange + Identified,
+        AE: AeadCipher + Identified
+{
+    let (sk, pk) = KEX::keypair_from_seed(&KAT_KEYPAIR_SEED);
+
+    for v in vectors {
+        let psk = match v.mode {
+            Mode::Psk => Some(KAT_PSK),
+            Mode::Base => None
+        };
+        let rng = NorxRng::from_seed(KAT_RNG_SEED);
+        let (enc, mut sender) = Hpke::<KEX, AE>::setup_sender(rng, &pk, KAT_INFO, psk).unwrap();
+        assert_eq!(enc.read_bytes(|x| x.to_vec()), hex(v.enc), "{:?}", v.mode);
+
+        let mut receiver = Hpke::<KEX, AE>::setup_receiver(&sk, &enc, KAT_INFO, psk).unwrap();
+        let mut schedule = Hpke::<KEX, AE>::key_schedule(v.mode, &hex(v.shared_secret), KAT_INFO, psk).unwrap();
+        let ae = AE::new(&hex(v.key));
+        let mut nonce = hex(v.base_nonce);
+
+        for (seq, (&aad, &expected)) in KAT_AAD.iter().zip(&[v.ct0, v.ct1]).enumerate() {
+            let expected = hex(expected);
+            let mut ct = vec![0; KAT_PT.len() + AE::TAG_LENGTH];
+            let mut ot = vec![0; KAT_PT.len()];
+
+            sender.seal(aad, KAT_PT, &mut ct).unwrap();
+            assert_eq!(ct, expected, "{:?} {}", v.mode, seq);
+
+            schedule.seal(aad, KAT_PT, &mut ct).unwrap();
+            assert_eq!(ct, expected, "{:?} {}", v.mode, seq);
+
+            let last = nonce.len() - 1;
+            nonce[last] ^= seq as u8;
+            ae.seal(&nonce, aad, KAT_PT, &mut ct).unwrap();
+            assert_eq!(ct, expected, "{:?} {}", v.mode, seq);
+
+            receiver.open(aad, &expected, &mut ot).unwrap();
+            assert_eq!(ot, KAT_PT);
+        }
+
+        assert_eq!(sender.export(KAT_CONTEXT, 32).unwrap(), hex(v.exported), "{:?}", v.mode);
+        assert_eq!(receiver.export(KAT_CONTEXT, 32).unwrap(), hex(v.exported), "{:?}", v.mode);
+        assert_eq!(schedule.export(KAT_CONTEXT, 32).unwrap(), hex(v.exported), "{:?}", v.mode);
+    }
+}
+
+
+#[test]
+fn test_kyber_norx() {
+    test_hpke::<Kyber, Norx6441>();
+    test_mismatch::<Kyber, Norx6441>();
+    test_order::<Kyber, Norx6441>();
+    test_psk_inputs::<Kyber, Norx6441>();
+}
+
+#[test]
+fn test_kyber_norxmrs() {
+    test_hpke::<Kyber, NorxMRS>();
+    test_mismatch::<Kyber, NorxMRS>();
+    test_order::<Kyber, NorxMRS>();
+}
+
+#[test]
+fn test_kat_kyber_norx() {
+    test_kat::<Kyber, Norx6441>(&KAT_KYBER_NORX);
+}
+
+#[test]
+fn test_kat_kyber_norxmrs() {
+    test_kat::<Kyber, NorxMRS>(&KAT_KYBER_NORXMRS);
+}
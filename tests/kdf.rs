@@ -0,0 +1,77 @@
+extern crate rand;
+extern crate sarkara;
+
+use rand::{ Rng, RngCore, FromEntropy, ChaChaRng };
+use sarkara::kdf::Kdf;
+use sarkara::kdf::norx::NorxKdf;
+use sarkara::kdf::balloon::{ balloon, Params };
+
+
+fn test_kdf<KDF: Kdf>() {
+    let mut rng = ChaChaRng::from_entropy();
+
+    // shorter outputs collide too often for `assert_ne`
+    for i in 16..256 {
+        let mut salt = vec![0u8; rng.gen_range(0, 34)];
+        let mut ikm = vec![0u8; 32];
+        let mut info = vec![0u8; rng.gen_range(0, 34)];
+        rng.fill_bytes(&mut salt);
+        rng.fill_bytes(&mut ikm);
+        rng.fill_bytes(&mut info);
+
+        let mut a = vec![0u8; i];
+        let mut b = vec![0u8; i];
+        let mut c = vec![0u8; i];
+        let mut d = vec![0u8; i + 1];
+
+        let kdf = KDF::extract(&salt, &ikm);
+        kdf.expand(&info, &mut a);
+        KDF::derive(&salt, &ikm, &info, &mut b);
+        assert_eq!(a, b);
+
+        info.push(0x42);
+        kdf.expand(&info, &mut c);
+        assert_ne!(a, c);
+
+        info.pop();
+        kdf.expand(&info, &mut d);
+        assert_ne!(a, &d[..i]);
+
+        ikm[0] ^= 0x42;
+        KDF::derive(&salt, &ikm, &info, &mut c);
+        assert_ne!(a, c);
+    }
+}
+
+
+#[test]
+fn test_norx_kdf() {
+    test_kdf::<NorxKdf>();
+}
+
+#[test]
+fn test_balloon() {
+    let params = Params { space_cost: 64, time_cost: 2 };
+    let mut a = [0; 32];
+    let mut b = [0; 32];
+    let mut c = [0; 48];
+
+    balloon(&params, b"password", b"salt", &mut a);
+    balloon(&params, b"password", b"salt", &mut b);
+    assert_eq!(a, b);
+
+    balloon(&params, b"password", b"salt", &mut c);
+    assert_eq!(&a[..], &c[..32]);
+
+    balloon(&params, b"passw0rd", b"salt", &mut b);
+    assert_ne!(a, b);
+
+    balloon(&params, b"password", b"sa1t", &mut b);
+    assert_ne!(a, b);
+
+    balloon(&Params { space_cost: 65, time_cost: 2 }, b"password", b"salt", &mut b);
+    assert_ne!(a, b);
+
+    balloon(&Params { space_cost: 64, time_cost: 3 }, b"password", b"salt", &mut b);
+    assert_ne!(a, b);
+}
@@ -1,9 +1,9 @@
 extern crate rand;
 extern crate sarkara;
 
-use rand::{ FromEntropy, ChaChaRng };
-use sarkara::{ Packing, Error };
-use sarkara::kex::{ KeyExchange, CheckedExchange };
+use rand::{ RngCore, FromEntropy, ChaChaRng };
+use sarkara::{ Packing, Error, SEED_LENGTH };
+use sarkara::kex::{ KeyExchange, CheckedExchange, SeedableExchange };
 use sarkara::kex::kyber::Kyber;
 
 
@@ -33,11 +33,47 @@ fn test_checkedkex<KEX: CheckedExchange>() {
 
     let r = <KEX as CheckedExchange>::exchange_from(&mut a, &ska, &fake_msg);
 
-    assert!(if let Err(Error::VerificationFailed) = r {
-        true
-    } else {
-        false
-    });
+    assert!(matches!(r, Err(Error::VerificationFailed)));
+}
+
+fn test_seedkex<KEX: SeedableExchange>() {
+    let (mut a, mut b) = (vec![0u8; KEX::SHARED_LENGTH], vec![0u8; KEX::SHARED_LENGTH]);
+    let mut rng = ChaChaRng::from_entropy();
+    let mut seed = [0; SEED_LENGTH];
+    rng.fill_bytes(&mut seed);
+
+    let (ska, pka) = KEX::keypair_from_seed(&seed);
+    let (skb, pkb) = KEX::keypair_from_seed(&seed);
+    assert!(ska.read_bytes(|x| skb.read_bytes(|y| x == y)));
+    assert!(pka.read_bytes(|x| pkb.read_bytes(|y| x == y)));
+
+    let msg = KEX::exchange_to(&mut rng, &mut b, &pka);
+    KEX::exchange_from(&mut a, &skb, &msg);
+    assert_eq!(a, b);
+
+    seed[0] ^= 0x42;
+    let (_, pkc) = KEX::keypair_from_seed(&seed);
+    assert!(pka.read_bytes(|x| pkc.read_bytes(|y| x != y)));
+}
+
+fn test_publickey<KEX: KeyExchange>() {
+    let mut rng = ChaChaRng::from_entropy();
+    let (ska, pka) = KEX::keypair(&mut rng);
+    let (skb, pkb) = KEX::keypair(&mut rng);
+
+    let pk = KEX::public_key(&ska);
+    assert!(pka.read_bytes(|x| pk.read_bytes(|y| x == y)));
+
+    assert!(KEX::check_keypair(&ska, &pka).is_ok());
+    assert!(KEX::check_keypair(&skb, &pkb).is_ok());
+    assert!(KEX::check_keypair(&ska, &pkb).is_err());
+    assert!(KEX::check_keypair(&skb, &pka).is_err());
+
+    // corrupted secret part
+    let mut buf = ska.read_bytes(|x| x.to_vec());
+    buf[0] ^= 0x42;
+    let ska = KEX::PrivateKey::from_bytes(&buf);
+    assert!(KEX::check_keypair(&ska, &pka).is_err());
 }
 
 
@@ -45,4 +81,20 @@ fn test_checkedkex<KEX: CheckedExchange>() {
 fn test_kyber() {
     test_kex::<Kyber>();
     test_checkedkex::<Kyber>();
+    test_seedkex::<Kyber>();
+    test_publickey::<Kyber>();
+}
+
+#[test]
+fn test_kyber_seed() {
+    let mut rng = ChaChaRng::from_entropy();
+    let (sk, _) = Kyber::keypair(&mut rng);
+    let seed = *sk.seed().unwrap();
+
+    let (sk2, _) = Kyber::keypair_from_seed(&seed);
+    assert!(sk == sk2);
+
+    let sk3 = sk.read_bytes(<Kyber as KeyExchange>::PrivateKey::from_bytes);
+    assert!(sk == sk3);
+    assert!(sk3.seed().is_none());
 }
@@ -0,0 +1,83 @@
+extern crate rand;
+extern crate sarkara;
+
+use rand::{ FromEntropy, ChaChaRng };
+use sarkara::{ Error, Decoding };
+use sarkara::armor::Armored;
+use sarkara::keyfile::{ self, Cipher };
+use sarkara::kdf::balloon::Params;
+use sarkara::kex::KeyExchange;
+use sarkara::sign::Signature;
+use sarkara::kex::kyber::Kyber;
+use sarkara::sign::dilithium::Dilithium;
+use sarkara::aead::norx6441::Norx6441;
+use sarkara::aead::norx_mrs::NorxMRS;
+
+
+const PARAMS: Params = Params { space_cost: 256, time_cost: 1 };
+const MAX: Params = Params { space_cost: 512, time_cost: 1 };
+
+fn test_keyfile<AE: Cipher, T: Armored, U: Armored>(key: &T) {
+    let mut rng = ChaChaRng::from_entropy();
+
+    let file = keyfile::save::<AE, _, _>(&mut rng, &PARAMS, b"correct horse", key).unwrap();
+    let key2: T = keyfile::load(&MAX, b"correct horse", &file).unwrap();
+    assert!(key.read_bytes(|x| key2.read_bytes(|y| x == y)));
+
+    assert!(matches!(
+        keyfile::load::<T>(&MAX, b"battery staple", &file),
+        Err(Error::WrongPassphrase)
+    ));
+
+    assert!(matches!(
+        keyfile::load::<U>(&MAX, b"correct horse", &file),
+        Err(Error::Decoding(Decoding::Label))
+    ));
+
+    assert!(keyfile::load::<T>(&MAX, b"correct horse", &file[..file.len() - 1]).is_err());
+
+    // params are authenticated
+    let mut file2 = file.clone();
+    file2[10] ^= 1;
+    assert!(matches!(
+        keyfile::load::<T>(&MAX, b"correct horse", &file2),
+        Err(Error::WrongPassphrase)
+    ));
+
+    // params above the caller maximum
+    let max = Params { space_cost: 128, time_cost: 1 };
+    assert!(matches!(
+        keyfile::load::<T>(&max, b"correct horse", &file),
+        Err(Error::Decoding(Decoding::Parameters))
+    ));
+
+    let params = Params { space_cost: 256, time_cost: keyfile::MAX_TIME_COST + 1 };
+    assert!(matches!(
+        keyfile::save::<AE, _, _>(&mut rng, &params, b"correct horse", key),
+        Err(Error::Decoding(Decoding::Parameters))
+    ));
+
+    let mut file2 = file.clone();
+    let len = file2.len();
+    file2[len - 1] ^= 1;
+    assert!(keyfile::load::<T>(&MAX, b"correct horse", &file2).is_err());
+}
+
+
+#[test]
+fn test_keyfile_kyber() {
+    let mut rng = ChaChaRng::from_entropy();
+    let (sk, _) = Kyber::keypair(&mut rng);
+
+    test_keyfile::<Norx6441, _, <Dilithium as Signature>::PrivateKey>(&sk);
+    test_keyfile::<NorxMRS, _, <Dilithium as Signature>::PrivateKey>(&sk);
+}
+
+#[test]
+fn test_keyfile_dilithium() {
+    let mut rng = ChaChaRng::from_entropy();
+    let (sk, _) = Dilithium::keypair(&mut rng);
+
+    test_keyfile::<Norx6441, _, <Kyber as KeyExchange>::PrivateKey>(&sk);
+    test_keyfile::<NorxMRS, _, <Kyber as KeyExchange>::PrivateKey>(&sk);
+}
@@ -0,0 +1,53 @@
+extern crate rand;
+extern crate sarkara;
+
+use rand::{ Rng, RngCore, FromEntropy, ChaChaRng };
+use sarkara::{ Error, Length };
+use sarkara::mac::Mac;
+use sarkara::mac::norx::NorxMac;
+
+
+fn test_mac<M: Mac>() {
+    let mut key = vec![0u8; M::KEY_LENGTH];
+    let mut rng = ChaChaRng::from_entropy();
+
+    for i in 1..256 {
+        let mut data = vec![0u8; i];
+        let mut tag = vec![0u8; M::TAG_LENGTH];
+        rng.fill_bytes(&mut key);
+        rng.fill_bytes(&mut data);
+        let split = rng.gen_range(0, i + 1);
+
+        let mut mac = M::new(&key).unwrap();
+        mac.update(&data);
+        mac.finalize(&mut tag);
+
+        let mut mac = M::new(&key).unwrap();
+        mac.update(&data[..split]);
+        mac.update(&data[split..]);
+        assert!(mac.verify(&tag).is_ok());
+
+        let mut mac = M::new(&key).unwrap();
+        mac.update(&data);
+        assert!(matches!(mac.verify(&tag[1..]), Err(Error::Length(Length::Tag))));
+
+        data[i - 1] ^= 0x42;
+        let mut mac = M::new(&key).unwrap();
+        mac.update(&data);
+        assert!(matches!(mac.verify(&tag), Err(Error::VerificationFailed)));
+
+        data[i - 1] ^= 0x42;
+        key[0] ^= 0x42;
+        let mut mac = M::new(&key).unwrap();
+        mac.update(&data);
+        assert!(mac.verify(&tag).is_err());
+    }
+
+    assert!(matches!(M::new(&key[1..]), Err(Error::Length(Length::Key))));
+}
+
+
+#[test]
+fn test_norx_mac() {
+    test_mac::<NorxMac>();
+}
@@ -0,0 +1,74 @@
+//! Build check for `sarkara` without `std`.
+
+#![no_std]
+
+use sarkara::aead::{ AeadCipher, norx6441::Norx6441 };
+use sarkara::hash::{ Hash, norx::NorxHash };
+use sarkara::kdf::{ Kdf, norx::NorxKdf };
+use sarkara::mac::{ Mac, norx::NorxMac };
+use sarkara::rand::norx::NorxRng;
+use sarkara::kex::{ KeyExchange, kyber::Kyber };
+use rand::{ RngCore, SeedableRng };
+
+
+pub fn check(seed: [u8; 32]) -> bool {
+    let mut rng = NorxRng::from_seed(seed);
+
+    let mut digest = [0; 32];
+    NorxHash::digest(&seed, &mut digest);
+
+    let mut key = [0; 32];
+    NorxKdf::derive(b"salt", &seed, b"info", &mut key);
+
+    let mut tag = [0; 32];
+    let mut mac = match NorxMac::new(&key) {
+        Ok(mac) => mac,
+        Err(_) => return false
+    };
+    mac.update(&digest);
+    mac.finalize(&mut tag);
+
+    let mut nonce = [0; 32];
+    rng.fill_bytes(&mut nonce);
+    let mut ciphertext = [0; 32 + 32];
+    let mut plaintext = [0; 32];
+    let cipher = Norx6441::new(&key);
+    if cipher.seal(&nonce[..Norx6441::NONCE_LENGTH], &tag, &digest, &mut ciphertext).is_err()
+        || cipher.open(&nonce[..Norx6441::NONCE_LENGTH], &tag, &ciphertext, &mut plaintext).is_err()
+    {
+        return false;
+    }
+
+    let (sk, pk) = Kyber::keypair(&mut rng);
+    let mut sharedkey = [0; 32];
+    let mut sharedkey2 = [0; 32];
+    let m = Kyber::exchange_to(&mut rng, &mut sharedkey, &pk);
+    Kyber::exchange_from(&mut sharedkey2, &sk, &m);
+
+    plaintext == digest && sharedkey == sharedkey2
+}
+
+#[cfg(feature = "alloc")]
+pub fn check_alloc(seed: [u8; 32]) -> bool {
+    use sarkara::sealedbox::SealedBox;
+    use sarkara::sign::{ Signature, dilithium::Dilithium };
+
+    let mut rng = NorxRng::from_seed(seed);
+    let (sk, pk) = Kyber::keypair(&mut rng);
+    let (m, sealing) = SealedBox::<Kyber, Norx6441>::send(&mut rng, &pk);
+    let opening = SealedBox::<Kyber, Norx6441>::recv(&sk, &m);
+
+    let nonce = [0; 32];
+    let mut ciphertext = [0; 32 + 32];
+    let mut plaintext = [0; 32];
+    if sealing.seal(&nonce[..Norx6441::NONCE_LENGTH], &[], &seed, &mut ciphertext).is_err()
+        || opening.open(&nonce[..Norx6441::NONCE_LENGTH], &[], &ciphertext, &mut plaintext).is_err()
+    {
+        return false;
+    }
+
+    let (sk, pk) = Dilithium::keypair(&mut rng);
+    let sig = Dilithium::signature(&mut rng, &sk, &seed);
+
+    plaintext == seed && Dilithium::verify(&pk, &sig, &seed).is_ok()
+}
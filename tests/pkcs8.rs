@@ -0,0 +1,50 @@
+extern crate rand;
+extern crate sarkara;
+
+use rand::{ FromEntropy, ChaChaRng };
+use sarkara::{ Packing, Error, Decoding };
+use sarkara::pkcs8::{ self, AlgorithmIdentifier };
+use sarkara::kex::KeyExchange;
+use sarkara::sign::Signature;
+use sarkara::kex::kyber::Kyber;
+use sarkara::sign::dilithium::Dilithium;
+
+
+fn test_pkcs8<A: AlgorithmIdentifier, B: AlgorithmIdentifier>(sk: &A::PrivateKey, pk: &A::PublicKey) {
+    let der = pkcs8::to_pkcs8::<A>(sk);
+    assert_eq!(der[0], 0x30);
+    let sk2 = pkcs8::from_pkcs8::<A>(&der).unwrap();
+    assert!(sk.read_bytes(|x| sk2.read_bytes(|y| x == y)));
+
+    assert!(matches!(pkcs8::from_pkcs8::<B>(&der), Err(Error::Decoding(Decoding::Algorithm))));
+    assert!(pkcs8::from_pkcs8::<A>(&der[..der.len() - 1]).is_err());
+    assert!(pkcs8::from_spki::<A>(&der).is_err());
+
+    let der = pkcs8::to_spki::<A>(pk);
+    assert_eq!(der[0], 0x30);
+    let pk2 = pkcs8::from_spki::<A>(&der).unwrap();
+    assert!(pk.read_bytes(|x| pk2.read_bytes(|y| x == y)));
+
+    assert!(matches!(pkcs8::from_spki::<B>(&der), Err(Error::Decoding(Decoding::Algorithm))));
+    assert!(pkcs8::from_spki::<A>(&der[..der.len() - 1]).is_err());
+    assert!(pkcs8::from_pkcs8::<A>(&der).is_err());
+
+    let mut der = der;
+    der.push(0);
+    assert!(pkcs8::from_spki::<A>(&der).is_err());
+}
+
+
+#[test]
+fn test_pkcs8_kyber() {
+    let mut rng = ChaChaRng::from_entropy();
+    let (sk, pk) = Kyber::keypair(&mut rng);
+    test_pkcs8::<Kyber, Dilithium>(&sk, &pk);
+}
+
+#[test]
+fn test_pkcs8_dilithium() {
+    let mut rng = ChaChaRng::from_entropy();
+    let (sk, pk) = Dilithium::keypair(&mut rng);
+    test_pkcs8::<Dilithium, Kyber>(&sk, &pk);
+}
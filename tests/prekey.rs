@@ -0,0 +1,133 @@
+extern crate rand;
+extern crate sarkara;
+
+use rand::{ FromEntropy, ChaChaRng };
+use sarkara::{ Packing, Error };
+use sarkara::kex::CheckedExchange;
+use sarkara::sign::Signature;
+use sarkara::prekey::{ Prekeys, Bundle, InitialMessage, initiate };
+
+use sarkara::kex::kyber::Kyber;
+use sarkara::sign::dilithium::Dilithium;
+
+
+fn test_prekey<KEX: CheckedExchange, SS: Signature>() {
+    let mut rng = ChaChaRng::from_entropy();
+    let alice = SS::keypair(&mut rng);
+    let identity = SS::keypair(&mut rng);
+    let mut bob = Prekeys::<KEX, SS>::new(&mut rng, identity);
+
+    // with a one-time prekey, over the wire
+    let bundle = bob.bundle(&mut rng).unwrap();
+    let bundle = Bundle::<KEX, SS>::from_bytes(&bundle.to_bytes()).unwrap();
+    assert_eq!(bob.one_time_prekeys(), 1);
+
+    let (message, alice_root) = initiate(&mut rng, &alice, &bundle).unwrap();
+    let message = InitialMessage::<KEX, SS>::from_bytes(&message.to_bytes()).unwrap();
+    let bob_root = bob.respond(&message).unwrap();
+    assert_eq!(alice_root.as_bytes(), bob_root.as_bytes());
+    assert!(message.identity.read_bytes(|x| alice.1.read_bytes(|y| x == y)));
+
+    // the one-time prekey is consumed
+    assert_eq!(bob.one_time_prekeys(), 0);
+    assert!(matches!(bob.respond(&message), Err(Error::InvalidState)));
+
+    // without a one-time prekey
+    let bundle = Bundle { one_time_prekey: None, ..bob.bundle(&mut rng).unwrap() };
+    let (message, alice_root) = initiate(&mut rng, &alice, &bundle).unwrap();
+    assert!(message.one_time_prekey.is_none());
+    let bob_root = bob.respond(&message).unwrap();
+    assert_eq!(alice_root.as_bytes(), bob_root.as_bytes());
+    let bob_root = bob.respond(&message).unwrap();
+    assert_eq!(alice_root.as_bytes(), bob_root.as_bytes());
+
+    // the previous signed prekey survives one rotation
+    let bundle = bob.bundle(&mut rng).unwrap();
+    let (message, alice_root) = initiate(&mut rng, &alice, &bundle).unwrap();
+    bob.rotate_signed_prekey(&mut rng).unwrap();
+    assert_eq!(bob.respond(&message).unwrap().as_bytes(), alice_root.as_bytes());
+
+    let bundle = Bundle { one_time_prekey: None, ..bob.bundle(&mut rng).unwrap() };
+    let (message, _) = initiate(&mut rng, &alice, &bundle).unwrap();
+    bob.rotate_signed_prekey(&mut rng).unwrap();
+    bob.rotate_signed_prekey(&mut rng).unwrap();
+    assert!(matches!(bob.respond(&message), Err(Error::InvalidState)));
+}
+
+fn test_forgery<KEX: CheckedExchange, SS: Signature>() {
+    let mut rng = ChaChaRng::from_entropy();
+    let alice = SS::keypair(&mut rng);
+    let eve = SS::keypair(&mut rng);
+    let identity = SS::keypair(&mut rng);
+    let mut bob = Prekeys::<KEX, SS>::new(&mut rng, identity);
+
+    // prekey not signed by the bundle identity
+    let mut bundle = bob.bundle(&mut rng).unwrap();
+    bundle.identity = eve.1.read_bytes(SS::PublicKey::from_bytes);
+    assert!(initiate(&mut rng, &alice, &bundle).is_err());
+
+    // prekey replaced
+    let mut bundle = bob.bundle(&mut rng).unwrap();
+    bundle.signed_prekey.key = KEX::keypair(&mut rng).1;
+    assert!(initiate(&mut rng, &alice, &bundle).is_err());
+
+    // initiator identity replaced, the one-time prekey is kept
+    let bundle = bob.bundle(&mut rng).unwrap();
+    let (mut message, _) = initiate(&mut rng, &alice, &bundle).unwrap();
+    message.identity = eve.1.read_bytes(SS::PublicKey::from_bytes);
+    let n = bob.one_time_prekeys();
+    assert!(bob.respond(&message).is_err());
+    assert_eq!(bob.one_time_prekeys(), n);
+
+    // malformed encodings
+    let bytes = message.to_bytes();
+    assert!(InitialMessage::<KEX, SS>::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    let mut bytes = bytes;
+    bytes.push(0);
+    assert!(InitialMessage::<KEX, SS>::from_bytes(&bytes).is_err());
+}
+
+fn test_store<KEX: CheckedExchange, SS: Signature>() {
+    let mut rng = ChaChaRng::from_entropy();
+    let alice = SS::keypair(&mut rng);
+    let identity = SS::keypair(&mut rng);
+    let mut bob = Prekeys::<KEX, SS>::new(&mut rng, identity);
+
+    // pending messages survive a restart
+    let bundle = bob.bundle(&mut rng).unwrap();
+    let (message, alice_root) = initiate(&mut rng, &alice, &bundle).unwrap();
+    bob.rotate_signed_prekey(&mut rng).unwrap();
+    bob.generate_one_time_prekeys(&mut rng, 3).unwrap();
+    let bytes = bob.to_bytes();
+    let mut bob = Prekeys::<KEX, SS>::from_bytes(&bytes).unwrap();
+    assert_eq!(bob.one_time_prekeys(), 4);
+    assert_eq!(bob.respond(&message).unwrap().as_bytes(), alice_root.as_bytes());
+    assert_eq!(bob.one_time_prekeys(), 3);
+
+    // ids are not reused
+    let bundle = bob.bundle(&mut rng).unwrap();
+    assert!(bundle.one_time_prekey.unwrap().id > bundle.signed_prekey.id);
+
+    // malformed encodings
+    assert!(Prekeys::<KEX, SS>::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    let mut extended = bytes.clone();
+    extended.push(0);
+    assert!(Prekeys::<KEX, SS>::from_bytes(&extended).is_err());
+
+    // exhausted ids
+    let identity = SS::keypair(&mut rng);
+    let mut bytes = Prekeys::<KEX, SS>::new(&mut rng, identity).to_bytes();
+    let len = bytes.len();
+    bytes[len - 8..len - 4].copy_from_slice(&[0xff; 4]);
+    let mut bob = Prekeys::<KEX, SS>::from_bytes(&bytes).unwrap();
+    assert!(matches!(bob.generate_one_time_prekeys(&mut rng, 1), Err(Error::InvalidState)));
+    assert!(matches!(bob.rotate_signed_prekey(&mut rng), Err(Error::InvalidState)));
+}
+
+
+#[test]
+fn test_kyber_dilithium() {
+    test_prekey::<Kyber, Dilithium>();
+    test_forgery::<Kyber, Dilithium>();
+    test_store::<Kyber, Dilithium>();
+}
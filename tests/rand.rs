@@ -0,0 +1,67 @@
+extern crate rand;
+extern crate sarkara;
+
+use rand::{ Rng, RngCore, SeedableRng, FromEntropy, ChaChaRng };
+use sarkara::rand::RESEED_INTERVAL;
+use sarkara::rand::norx::NorxRng;
+use sarkara::kex::KeyExchange;
+use sarkara::kex::kyber::Kyber;
+use sarkara::Packing;
+
+
+#[test]
+fn test_norx_rng_seeded() {
+    let mut rng = ChaChaRng::from_entropy();
+    let mut seed = [0; 32];
+    rng.fill_bytes(&mut seed);
+
+    let mut a = NorxRng::from_seed(seed);
+    let mut b = NorxRng::from_seed(seed);
+
+    for _ in 0..64 {
+        let mut x = vec![0u8; rng.gen_range(0, 300)];
+        let mut y = vec![0u8; x.len()];
+        a.fill_bytes(&mut x);
+        b.fill_bytes(&mut y);
+        assert_eq!(x, y);
+        assert_eq!(a.next_u64(), b.next_u64());
+    }
+    assert_eq!(a.reseed_counter(), 128);
+
+    a.reseed(b"entropy");
+    b.reseed(b"entropy");
+    assert_eq!(a.reseed_counter(), 0);
+    assert_eq!(a.next_u32(), b.next_u32());
+
+    b.reseed(b"other entropy");
+    assert_ne!(a.next_u64(), b.next_u64());
+
+    let mut c = NorxRng::from_seed(seed);
+    seed[0] ^= 0x42;
+    let mut d = NorxRng::from_seed(seed);
+    assert_ne!(c.next_u64(), d.next_u64());
+}
+
+#[test]
+fn test_norx_rng_keypair() {
+    let mut a = NorxRng::from_seed([0x42; 32]);
+    let mut b = NorxRng::from_seed([0x42; 32]);
+
+    let (_, pka) = Kyber::keypair(&mut a);
+    let (_, pkb) = Kyber::keypair(&mut b);
+    assert!(pka.read_bytes(|x| pkb.read_bytes(|y| x == y)));
+}
+
+#[test]
+fn test_norx_rng_reseed() {
+    let mut rng = NorxRng::new().unwrap();
+    let mut buf = [0; 16];
+
+    for _ in 0..RESEED_INTERVAL {
+        rng.fill_bytes(&mut buf);
+    }
+    assert_eq!(rng.reseed_counter(), RESEED_INTERVAL);
+
+    rng.fill_bytes(&mut buf);
+    assert_eq!(rng.reseed_counter(), 1);
+}
@@ -0,0 +1,138 @@
+extern crate rand;
+extern crate sarkara;
+
+use rand::{ Rng, RngCore, FromEntropy, ChaChaRng };
+use sarkara::{ Error, Length };
+use sarkara::aead::AeadCipher;
+use sarkara::kex::CheckedExchange;
+use sarkara::ratchet::{ Ratchet, ROOT_KEY_LENGTH, MAX_SKIP, MAX_SKIPPED_KEYS };
+
+use sarkara::kex::kyber::Kyber;
+use sarkara::aead::norx6441::Norx6441;
+
+
+fn pair<KEX: CheckedExchange, AE: AeadCipher>(rng: &mut ChaChaRng) -> (Ratchet<KEX, AE>, Ratchet<KEX, AE>) {
+    let mut root = [0; ROOT_KEY_LENGTH];
+    rng.fill_bytes(&mut root);
+    let (sk, pk) = KEX::keypair(&mut *rng);
+
+    let alice = Ratchet::initiator(&root, pk).unwrap();
+    let pk2 = KEX::public_key(&sk);
+    let bob = Ratchet::responder(&root, (sk, pk2)).unwrap();
+    (alice, bob)
+}
+
+fn send<KEX: CheckedExchange, AE: AeadCipher>(rng: &mut ChaChaRng, x: &mut Ratchet<KEX, AE>, n: usize) -> Vec<(Vec<u8>, Vec<u8>)> {
+    (0..n)
+        .map(|_| {
+            let mut pt = vec![0; rng.gen_range(0, 128)];
+            rng.fill_bytes(&mut pt);
+            let ct = x.encrypt(&mut *rng, b"ad", &pt).unwrap();
+            (pt, ct)
+        })
+        .collect()
+}
+
+fn test_ratchet<KEX: CheckedExchange, AE: AeadCipher>() {
+    let mut rng = ChaChaRng::from_entropy();
+    let (mut alice, mut bob) = pair::<KEX, AE>(&mut rng);
+
+    // the responder waits for the first message
+    assert!(matches!(bob.encrypt(&mut rng, &[], &[]), Err(Error::InvalidState)));
+
+    for _ in 0..3 {
+        for (pt, ct) in send(&mut rng, &mut alice, 3) {
+            assert_eq!(bob.decrypt(b"ad", &ct).unwrap(), pt);
+        }
+        for (pt, ct) in send(&mut rng, &mut bob, 3) {
+            assert_eq!(alice.decrypt(b"ad", &ct).unwrap(), pt);
+        }
+    }
+    assert_eq!(alice.skipped_keys(), 0);
+    assert_eq!(bob.skipped_keys(), 0);
+}
+
+fn test_out_of_order<KEX: CheckedExchange, AE: AeadCipher>() {
+    let mut rng = ChaChaRng::from_entropy();
+    let (mut alice, mut bob) = pair::<KEX, AE>(&mut rng);
+
+    // within an epoch
+    let mut msgs = send(&mut rng, &mut alice, 5);
+    msgs.reverse();
+    let (pt, ct) = msgs.remove(0);
+    assert_eq!(bob.decrypt(b"ad", &ct).unwrap(), pt);
+    assert_eq!(bob.skipped_keys(), 4);
+
+    // across epochs
+    for (pt, ct) in send(&mut rng, &mut bob, 2) {
+        assert_eq!(alice.decrypt(b"ad", &ct).unwrap(), pt);
+    }
+    let late = send(&mut rng, &mut alice, 2);
+    for (pt, ct) in send(&mut rng, &mut alice, 1) {
+        assert_eq!(bob.decrypt(b"ad", &ct).unwrap(), pt);
+    }
+    assert_eq!(bob.skipped_keys(), 6);
+
+    for (pt, ct) in late.into_iter().chain(msgs) {
+        assert_eq!(bob.decrypt(b"ad", &ct).unwrap(), pt);
+
+        // replay
+        assert!(bob.decrypt(b"ad", &ct).is_err());
+    }
+    assert_eq!(bob.skipped_keys(), 0);
+}
+
+fn test_tamper<KEX: CheckedExchange, AE: AeadCipher>() {
+    let mut rng = ChaChaRng::from_entropy();
+    let (mut alice, mut bob) = pair::<KEX, AE>(&mut rng);
+
+    let (pt, ct) = send(&mut rng, &mut alice, 1).pop().unwrap();
+
+    // the state is unchanged by a failed decryption
+    let mut bad = ct.clone();
+    let last = bad.len() - 1;
+    bad[last] ^= 0x42;
+    assert!(matches!(bob.decrypt(b"ad", &bad), Err(Error::VerificationFailed)));
+    assert!(bob.decrypt(b"other", &ct).is_err());
+    assert!(matches!(bob.decrypt(b"ad", &ct[..100]), Err(Error::Length(Length::Encoding))));
+    assert_eq!(bob.decrypt(b"ad", &ct).unwrap(), pt);
+
+    // a header for an unknown key
+    let (_, mut eve) = pair::<KEX, AE>(&mut rng);
+    let (_, ct) = send(&mut rng, &mut bob, 1).pop().unwrap();
+    assert!(eve.decrypt(b"ad", &ct).is_err());
+}
+
+fn test_bounds<KEX: CheckedExchange, AE: AeadCipher>() {
+    let mut rng = ChaChaRng::from_entropy();
+    let (mut alice, mut bob) = pair::<KEX, AE>(&mut rng);
+
+    let (_, ct) = send(&mut rng, &mut alice, MAX_SKIP as usize + 2).pop().unwrap();
+    assert!(matches!(bob.decrypt(b"ad", &ct), Err(Error::InvalidState)));
+    assert_eq!(bob.skipped_keys(), 0);
+
+    let (mut alice, mut bob) = pair::<KEX, AE>(&mut rng);
+    for i in 1..4 {
+        let (pt, ct) = send(&mut rng, &mut alice, MAX_SKIP as usize + 1).pop().unwrap();
+        assert_eq!(bob.decrypt(b"ad", &ct).unwrap(), pt);
+        assert_eq!(bob.skipped_keys(), MAX_SKIPPED_KEYS.min(i * MAX_SKIP as usize));
+
+        let (pt, ct) = send(&mut rng, &mut bob, 1).pop().unwrap();
+        assert_eq!(alice.decrypt(b"ad", &ct).unwrap(), pt);
+    }
+
+    assert!(Ratchet::<KEX, AE>::initiator(&[0; 16], KEX::keypair(&mut rng).1).is_err());
+}
+
+
+#[test]
+fn test_kyber_norx() {
+    test_ratchet::<Kyber, Norx6441>();
+    test_out_of_order::<Kyber, Norx6441>();
+    test_tamper::<Kyber, Norx6441>();
+}
+
+#[test]
+fn test_kyber_norx_bounds() {
+    test_bounds::<Kyber, Norx6441>();
+}
@@ -0,0 +1,91 @@
+extern crate rand;
+extern crate sarkara;
+
+use rand::{ Rng, RngCore, FromEntropy, ChaChaRng };
+use sarkara::{ Error, Length, Decoding };
+use sarkara::registry::{ self, Algorithm, Kind };
+
+
+#[test]
+fn test_algorithm() {
+    for &alg in Algorithm::ALL {
+        assert_eq!(Algorithm::from_id(alg.id()), Some(alg));
+        assert_eq!(alg.name().parse::<Algorithm>(), Ok(alg));
+        assert_eq!(alg.to_string().to_uppercase().parse::<Algorithm>(), Ok(alg));
+
+        match alg.kind() {
+            Kind::KeyExchange => assert!(registry::key_exchange(alg).is_some()),
+            Kind::Signature => assert!(registry::signature(alg).is_some()),
+            Kind::Aead => assert!(registry::aead(alg).is_some())
+        }
+    }
+
+    assert_eq!(Algorithm::from_id(0), None);
+    assert_eq!("rsa".parse::<Algorithm>(), Err(Error::Decoding(Decoding::Algorithm)));
+    assert!(registry::aead(Algorithm::Kyber).is_none());
+}
+
+#[test]
+fn test_key_exchange() {
+    let mut rng = ChaChaRng::from_entropy();
+    let kex = registry::key_exchange("kyber".parse().unwrap()).unwrap();
+
+    let (sk, pk) = kex.keypair(&mut rng);
+    assert_eq!(sk.len(), kex.private_key_length());
+    assert_eq!(pk.len(), kex.public_key_length());
+    assert_eq!(kex.public_key(&sk).unwrap(), pk);
+
+    let mut a = vec![0; kex.shared_length()];
+    let mut b = vec![0; kex.shared_length()];
+    let m = kex.exchange_to(&mut rng, &mut a, &pk).unwrap();
+    assert_eq!(m.len(), kex.message_length());
+    kex.exchange_from(&mut b, &sk, &m).unwrap();
+    assert_eq!(a, b);
+
+    assert_eq!(kex.exchange_to(&mut rng, &mut a, &pk[1..]), Err(Error::Length(Length::Key)));
+    assert_eq!(kex.exchange_from(&mut b, &sk, &m[1..]), Err(Error::Length(Length::Encoding)));
+}
+
+#[test]
+fn test_signature() {
+    let mut rng = ChaChaRng::from_entropy();
+    let mut data = vec![0; rng.gen_range(1, 2049)];
+    rng.fill_bytes(&mut data);
+    let sign = registry::signature("dilithium".parse().unwrap()).unwrap();
+
+    let (sk, pk) = sign.keypair(&mut rng);
+    assert_eq!(sign.public_key(&sk).unwrap(), pk);
+    let sig = sign.signature(&mut rng, &sk, &data).unwrap();
+    assert_eq!(sig.len(), sign.signature_length());
+    assert!(sign.verify(&pk, &sig, &data).is_ok());
+
+    data[0] ^= 0x42;
+    assert_eq!(sign.verify(&pk, &sig, &data), Err(Error::InvalidSignature));
+    assert_eq!(sign.signature(&mut rng, &pk, &data), Err(Error::Length(Length::Key)));
+}
+
+#[test]
+fn test_aead() {
+    let mut rng = ChaChaRng::from_entropy();
+
+    for name in &["norx6441", "norxmrs"] {
+        let ae = registry::aead(name.parse().unwrap()).unwrap();
+        let mut key = vec![0; ae.key_length()];
+        let mut nonce = vec![0; ae.nonce_length()];
+        let mut pt = vec![0; rng.gen_range(1, 2049)];
+        rng.fill_bytes(&mut key);
+        rng.fill_bytes(&mut nonce);
+        rng.fill_bytes(&mut pt);
+
+        let mut ct = vec![0; pt.len() + ae.tag_length()];
+        let mut ot = vec![0; pt.len()];
+        ae.seal(&key, &nonce, &[], &pt, &mut ct).unwrap();
+        ae.open(&key, &nonce, &[], &ct, &mut ot).unwrap();
+        assert_eq!(pt, ot);
+
+        ct[0] ^= 0x42;
+        assert_eq!(ae.open(&key, &nonce, &[], &ct, &mut ot), Err(Error::VerificationFailed));
+        assert_eq!(ae.seal(&key[1..], &nonce, &[], &pt, &mut ct), Err(Error::Length(Length::Key)));
+        assert_eq!(ae.seal(&key, &nonce[1..], &[], &pt, &mut ct), Err(Error::Length(Length::Nonce)));
+    }
+}
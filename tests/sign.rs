@@ -2,7 +2,11 @@ extern crate rand;
 extern crate sarkara;
 
 use rand::{ Rng, RngCore, FromEntropy, ChaChaRng };
-use sarkara::sign::{ Signature, DeterministicSignature };
+use sarkara::{ Packing, Error, Length, SEED_LENGTH };
+use sarkara::sign::{
+    Signature, DeterministicSignature, SeedableSignature,
+    ContextSignature, ParallelVerify
+};
 use sarkara::sign::dilithium::Dilithium;
 
 
@@ -16,7 +20,7 @@ fn test_sign<SS: Signature>() {
     assert!(SS::verify(&pk, &sig, &data).is_ok());
 
     data[0] ^= 0x42;
-    assert!(SS::verify(&pk, &sig, &data).is_err());
+    assert_eq!(SS::verify(&pk, &sig, &data), Err(Error::InvalidSignature));
 }
 
 fn test_dsign<SS: DeterministicSignature>() {
@@ -32,9 +36,240 @@ fn test_dsign<SS: DeterministicSignature>() {
     assert!(SS::verify(&pk, &sig, &data).is_err());
 }
 
+fn test_seedsign<SS: SeedableSignature>() {
+    let mut rng = ChaChaRng::from_entropy();
+    let mut data = vec![0; rng.gen_range(1, 2049)];
+    let mut seed = [0; SEED_LENGTH];
+    rng.fill_bytes(&mut data);
+    rng.fill_bytes(&mut seed);
+
+    let (ska, pka) = SS::keypair_from_seed(&seed);
+    let (skb, pkb) = SS::keypair_from_seed(&seed);
+    assert!(ska.read_bytes(|x| skb.read_bytes(|y| x == y)));
+    assert!(pka.read_bytes(|x| pkb.read_bytes(|y| x == y)));
+
+    let sig = SS::signature(&mut rng, &skb, &data);
+    assert!(SS::verify(&pka, &sig, &data).is_ok());
+}
+
+fn test_context<SS: ContextSignature>() {
+    let mut rng = ChaChaRng::from_entropy();
+    let mut data = vec![0; rng.gen_range(1, 2049)];
+    rng.fill_bytes(&mut data);
+
+    let (sk, pk) = SS::keypair(&mut rng);
+    let sig = SS::signature_with_context(&sk, b"release", &data).unwrap();
+    assert!(SS::verify_with_context(&pk, &sig, b"release", &data).is_ok());
+    assert_eq!(SS::verify_with_context(&pk, &sig, b"token", &data), Err(Error::InvalidSignature));
+    assert_eq!(SS::verify(&pk, &sig, &data), Err(Error::InvalidSignature));
+
+    // the empty context is the plain signature
+    let sig = SS::signature(&mut rng, &sk, &data);
+    assert!(SS::verify_with_context(&pk, &sig, &[], &data).is_ok());
+    assert!(SS::verify_with_context(&pk, &sig, b"release", &data).is_err());
+
+    // a signature over something that looks like a frame is not bound to that context
+    let mut framed = vec![0x00, 7];
+    framed.extend_from_slice(b"release");
+    framed.extend_from_slice(&data);
+    let sig = SS::signature(&mut rng, &sk, &framed);
+    assert_eq!(SS::verify_with_context(&pk, &sig, b"release", &data), Err(Error::InvalidSignature));
+    let sig = SS::signature_with_context(&sk, &[], &framed).unwrap();
+    assert_eq!(SS::verify_with_context(&pk, &sig, b"release", &data), Err(Error::InvalidSignature));
+
+    let ctx = [0; 256];
+    assert!(matches!(SS::signature_with_context(&sk, &ctx, &data), Err(Error::Length(Length::Context))));
+    assert!(SS::signature_with_context(&sk, &ctx[..255], &data).is_ok());
+}
+
+fn test_parallel<SS: ParallelVerify>()
+    where SS::PublicKey: Sync, SS::Signature: Sync
+{
+    let mut rng = ChaChaRng::from_entropy();
+    let keys = (0..3).map(|_| SS::keypair(&mut rng)).collect::<Vec<_>>();
+    let mut data = (0..20)
+        .map(|_| {
+            let mut data = vec![0; rng.gen_range(1, 257)];
+            rng.fill_bytes(&mut data);
+            data
+        })
+        .collect::<Vec<_>>();
+    let sigs = data.iter()
+        .enumerate()
+        .map(|(i, data)| SS::signature(&mut rng, &keys[i % keys.len()].0, data))
+        .collect::<Vec<_>>();
+
+    {
+        let items = data.iter()
+            .zip(&sigs)
+            .enumerate()
+            .map(|(i, (data, sig))| (&keys[i % keys.len()].1, sig, &data[..]))
+            .collect::<Vec<_>>();
+        assert!(SS::verify_each(&items).iter().all(Result::is_ok));
+        assert!(SS::verify_all(&items).is_ok());
+        assert!(SS::verify_all(&[]).is_ok());
+    }
+
+    data[7][0] ^= 0x42;
+    let items = data.iter()
+        .zip(&sigs)
+        .enumerate()
+        .map(|(i, (data, sig))| (&keys[i % keys.len()].1, sig, &data[..]))
+        .collect::<Vec<_>>();
+    let results = SS::verify_each(&items);
+    assert_eq!(results.len(), items.len());
+    for (i, result) in results.iter().enumerate() {
+        assert_eq!(result.is_ok(), i != 7);
+    }
+    assert_eq!(SS::verify_all(&items), Err(Error::InvalidSignature));
+}
+
+fn test_publickey<SS: Signature>() {
+    let mut rng = ChaChaRng::from_entropy();
+    let (ska, pka) = SS::keypair(&mut rng);
+    let (skb, pkb) = SS::keypair(&mut rng);
+
+    let pk = SS::public_key(&ska);
+    assert!(pka.read_bytes(|x| pk.read_bytes(|y| x == y)));
+
+    let ska = ska.read_bytes(SS::PrivateKey::from_bytes);
+    assert!(SS::check_keypair(&ska, &pka).is_ok());
+    assert!(SS::check_keypair(&skb, &pkb).is_ok());
+    assert_eq!(SS::check_keypair(&ska, &pkb), Err(Error::KeyMismatch));
+    assert!(SS::check_keypair(&skb, &pka).is_err());
+
+    // corrupted secret part
+    let mut buf = ska.read_bytes(|x| x.to_vec());
+    buf[0] ^= 0x42;
+    let ska = SS::PrivateKey::from_bytes(&buf);
+    assert_eq!(SS::check_keypair(&ska, &pka), Err(Error::KeyMismatch));
+}
+
 
 #[test]
 fn test_dilithium() {
     test_sign::<Dilithium>();
     test_dsign::<Dilithium>();
+    test_seedsign::<Dilithium>();
+    test_context::<Dilithium>();
+    test_parallel::<Dilithium>();
+    test_publickey::<Dilithium>();
+}
+
+#[test]
+fn test_dilithium_seed() {
+    let mut rng = ChaChaRng::from_entropy();
+    let (sk, _) = Dilithium::keypair(&mut rng);
+    let seed = *sk.seed().unwrap();
+
+    let (sk2, _) = Dilithium::keypair_from_seed(&seed);
+    assert!(sk == sk2);
+
+    let sk3 = sk.read_bytes(<Dilithium as Signature>::PrivateKey::from_bytes);
+    assert!(sk == sk3);
+    assert!(sk3.seed().is_none());
+}
+
+#[test]
+fn test_dilithium_prehash() {
+    use std::io::Write;
+    use sarkara::hash::{ Hash, norx::NorxHash };
+    use sarkara::sign::{ PrehashSignature, Signer, Verifier };
+
+    let mut rng = ChaChaRng::from_entropy();
+    let mut data = vec![0; rng.gen_range(1, 1 << 16)];
+    rng.fill_bytes(&mut data);
+
+    let (sk, pk) = Dilithium::keypair(&mut rng);
+
+    let mut signer = Signer::<Dilithium>::new(&sk);
+    for chunk in data.chunks(1000) {
+        signer.write_all(chunk).unwrap();
+    }
+    let sig = signer.finalize();
+
+    let mut hash = NorxHash::new();
+    hash.update(&data);
+    assert!(Dilithium::verify_prehashed(&pk, &sig, &[], hash).is_ok());
+
+    let mut verifier = Verifier::<Dilithium>::new(&pk);
+    verifier.update(&data);
+    assert!(verifier.finalize(&sig).is_ok());
+
+    // prehashed and pure signatures are not interchangeable
+    assert_eq!(Dilithium::verify(&pk, &sig, &data), Err(Error::InvalidSignature));
+    let sig = <Dilithium as Signature>::signature(&mut rng, &sk, &data);
+    let mut verifier = Verifier::<Dilithium>::new(&pk);
+    verifier.update(&data);
+    assert_eq!(verifier.finalize(&sig), Err(Error::InvalidSignature));
+
+    let mut signer = Signer::<Dilithium>::with_context(&sk, b"image").unwrap();
+    signer.update(&data);
+    let sig = signer.finalize();
+    let mut verifier = Verifier::<Dilithium>::with_context(&pk, b"image").unwrap();
+    verifier.update(&data);
+    assert!(verifier.finalize(&sig).is_ok());
+    let mut verifier = Verifier::<Dilithium>::new(&pk);
+    verifier.update(&data);
+    assert_eq!(verifier.finalize(&sig), Err(Error::InvalidSignature));
+    assert!(Verifier::<Dilithium>::with_context(&pk, &[0; 256]).is_err());
+    let signer = Signer::<Dilithium>::with_context(&sk, &[0; 255]).unwrap();
+    let sig = signer.finalize();
+    let verifier = Verifier::<Dilithium>::with_context(&pk, &[0; 255]).unwrap();
+    assert!(verifier.finalize(&sig).is_ok());
+
+    let mut verifier = Verifier::<Dilithium>::with_context(&pk, b"image").unwrap();
+    data[0] ^= 0x42;
+    verifier.update(&data);
+    assert_eq!(verifier.finalize(&sig), Err(Error::InvalidSignature));
+}
+
+#[test]
+fn test_dilithium_framing() {
+    use dilithium::{ params, sign };
+    use sarkara::hash::{ Hash, Xof, XofReader, norx::NorxHash };
+    use sarkara::sign::PrehashSignature;
+
+    let mut rng = ChaChaRng::from_entropy();
+    let (sk, pk) = Dilithium::keypair(&mut rng);
+    let mut sk_bytes = [0; params::SECRETKEYBYTES];
+    let mut pk_bytes = [0; params::PUBLICKEYBYTES];
+    let mut sig_bytes = [0; params::BYTES];
+    sk.read_bytes(|x| sk_bytes.copy_from_slice(&x[..params::SECRETKEYBYTES]));
+    pk.read_bytes(|x| pk_bytes.copy_from_slice(x));
+
+    // plain signatures are framed, `0x00 || 0 || message`
+    let sig = <Dilithium as DeterministicSignature>::signature(&sk, b"framing");
+    sig.read_bytes(|x| sig_bytes.copy_from_slice(x));
+    assert!(sign::verify(b"\x00\x00framing", &sig_bytes, &pk_bytes));
+    assert!(!sign::verify(b"framing", &sig_bytes, &pk_bytes));
+    assert_eq!(Dilithium::verify_legacy(&pk, &sig, b"framing"), Err(Error::InvalidSignature));
+
+    // unframed signatures only verify as legacy signatures
+    let mut sig_bytes = [0; params::BYTES];
+    sign::sign(&mut sig_bytes, b"framing", &sk_bytes);
+    let sig = <Dilithium as Signature>::Signature::from_bytes(&sig_bytes);
+    assert!(Dilithium::verify_legacy(&pk, &sig, b"framing").is_ok());
+    assert_eq!(Dilithium::verify(&pk, &sig, b"framing"), Err(Error::InvalidSignature));
+
+    // a plain signature over a prehash frame is not a prehashed signature
+    let mut digest = [0; 64];
+    let mut hash = NorxHash::new();
+    hash.update(b"framing");
+    hash.xof().read(&mut digest);
+    let mut frame = vec![0x01, 0x00, 17];
+    frame.extend_from_slice(b"sarkara norx hash");
+    frame.extend_from_slice(&digest);
+    let sig = <Dilithium as DeterministicSignature>::signature(&sk, &frame);
+    let mut hash = NorxHash::new();
+    hash.update(b"framing");
+    assert_eq!(Dilithium::verify_prehashed(&pk, &sig, &[], hash), Err(Error::InvalidSignature));
+
+    // while the frame itself is what the prehashed signature signs
+    let mut sig_bytes = [0; params::BYTES];
+    sign::sign(&mut sig_bytes, &frame, &sk_bytes);
+    let sig = <Dilithium as Signature>::Signature::from_bytes(&sig_bytes);
+    let mut hash = NorxHash::new();
+    hash.update(b"framing");
+    assert!(Dilithium::verify_prehashed(&pk, &sig, &[], hash).is_ok());
 }